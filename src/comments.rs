@@ -0,0 +1,269 @@
+//! Comments, their replies and the text range they are anchored to.
+use docx_rust::rels::Relationships;
+use hard_xml::XmlRead;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::utils::escape_html;
+use crate::wordml::{self, Parts};
+use crate::{
+    CommentStyle, DocxParserError, MarkdownDocument, MarkdownOptions, MarkdownParagraph,
+    RevisionMode, TextBlock, TextType,
+};
+
+/// A comment on a range of the document text
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Comment {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// The commented text
+    pub anchor: String,
+    pub content: Vec<MarkdownParagraph>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub replies: Vec<Comment>,
+}
+
+impl Comment {
+    fn from_comment(comment: &wordml::Comment, rels: Option<&Relationships>) -> Self {
+        Comment {
+            id: comment.id.to_string(),
+            author: comment.author.as_ref().map(|author| author.to_string()),
+            date: comment.date.as_ref().map(|date| date.to_string()),
+            anchor: String::new(),
+            content: comment
+                .content
+                .iter()
+                .map(|paragraph| MarkdownParagraph::from_paragraph(paragraph, rels))
+                .collect(),
+            replies: vec![],
+        }
+    }
+
+    /// Read all comments of the document, with the replies nested in the comment they reply to.
+    pub(crate) fn read_all(parts: &Parts) -> Result<Vec<Comment>, DocxParserError> {
+        let comments = match parts.get("word/comments.xml") {
+            Some(comments) => wordml::Comments::from_str(comments)?,
+            None => return Ok(vec![]),
+        };
+        let extended = match parts.get("word/commentsExtended.xml") {
+            Some(extended) => wordml::CommentsExtended::from_str(extended)?.comments,
+            None => vec![],
+        };
+        let rels = parts.relationships("word/comments.xml")?;
+
+        // Replies refer to the last paragraph of their parent comment
+        let para_ids: HashMap<&str, &str> = comments
+            .comments
+            .iter()
+            .filter_map(|comment| {
+                let para_id = comment.content.last()?.para_id.as_deref()?;
+                Some((para_id, comment.id.as_ref()))
+            })
+            .collect();
+        let parents: HashMap<&str, &str> = extended
+            .iter()
+            .filter_map(|comment| {
+                let id = para_ids.get(comment.para_id.as_ref())?;
+                let parent = para_ids.get(comment.para_id_parent.as_deref()?)?;
+                Some((*id, *parent))
+            })
+            .collect();
+
+        let mut threads: Vec<Comment> = vec![];
+        let mut replies: HashMap<&str, Vec<Comment>> = HashMap::new();
+        for comment in &comments.comments {
+            // Word links all replies to the first comment of the thread, but follow longer chains too
+            let mut root = comment.id.as_ref();
+            for _ in 0..parents.len() {
+                match parents.get(root) {
+                    Some(parent) if *parent != root => root = parent,
+                    _ => break,
+                }
+            }
+            let markdown_comment = Comment::from_comment(comment, rels.as_ref());
+            if root == comment.id {
+                threads.push(markdown_comment);
+            } else {
+                replies.entry(root).or_default().push(markdown_comment);
+            }
+        }
+        for thread in threads.iter_mut() {
+            thread.replies = replies.remove(thread.id.as_str()).unwrap_or_default();
+        }
+        Ok(threads)
+    }
+
+    /// The author and day of the comment, e.g. `Jane Doe (2024-01-31)`.
+    fn byline(&self) -> Option<String> {
+        let day = self.date.as_ref().and_then(|date| date.split('T').next());
+        match (&self.author, day) {
+            (Some(author), Some(day)) => Some(format!("{author} ({day})")),
+            (Some(author), None) => Some(author.to_string()),
+            (None, Some(day)) => Some(day.to_string()),
+            (None, None) => None,
+        }
+    }
+
+    /// The comment as Markdown, with its paragraphs joined by `separator`.
+    fn text(&self, doc: &MarkdownDocument, options: &MarkdownOptions, separator: &str) -> String {
+        let text = self
+            .content
+            .iter()
+            .map(|paragraph| paragraph.to_markdown(&doc.styles, &mut HashMap::new(), doc, options))
+            .filter(|paragraph| !paragraph.is_empty())
+            .collect::<Vec<_>>()
+            .join(separator);
+        match self.byline() {
+            Some(byline) => format!("{byline}: {text}"),
+            None => text,
+        }
+    }
+
+    /// This comment followed by its replies.
+    fn thread(&self) -> impl Iterator<Item = &Comment> {
+        std::iter::once(self).chain(self.replies.iter())
+    }
+
+    /// Render the start or end marker of the comment range, which is the first block of `blocks`.
+    /// `highlight` keeps track of the comment whose text is highlighted in CriticMarkup, as
+    /// highlights cannot be nested or span multiple paragraphs.
+    pub(crate) fn marker_to_markdown(
+        &self,
+        blocks: &[TextBlock],
+        highlight: &mut Option<String>,
+        doc: &MarkdownDocument,
+        options: &MarkdownOptions,
+    ) -> String {
+        let is_start = blocks[0].text_type == TextType::CommentStart;
+        match options.comments {
+            CommentStyle::None => String::new(),
+            CommentStyle::CriticMarkup if is_start => {
+                let ends_in_paragraph = blocks
+                    .iter()
+                    .any(|block| block.text_type == TextType::CommentEnd && block.text == self.id);
+                if highlight.is_none() && ends_in_paragraph {
+                    *highlight = Some(self.id.clone());
+                    "{==".to_string()
+                } else {
+                    String::new()
+                }
+            }
+            CommentStyle::CriticMarkup => {
+                let mut markdown = String::new();
+                if highlight.as_ref() == Some(&self.id) {
+                    *highlight = None;
+                    markdown += "==}";
+                }
+                for comment in self.thread() {
+                    markdown += &format!("{{>>{}<<}}", comment.text(doc, options, " "));
+                }
+                markdown
+            }
+            CommentStyle::Html if is_start => self
+                .thread()
+                .map(|comment| {
+                    let mut attributes = format!(r#"id="{}""#, escape_html(&comment.id));
+                    if let Some(author) = &comment.author {
+                        attributes += &format!(r#" author="{}""#, escape_html(author));
+                    }
+                    if let Some(date) = &comment.date {
+                        attributes += &format!(r#" date="{}""#, escape_html(date));
+                    }
+                    let text = comment
+                        .content
+                        .iter()
+                        .map(|paragraph| {
+                            paragraph.to_markdown(&doc.styles, &mut HashMap::new(), doc, options)
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" ");
+                    format!(r#"<span class="comment-start" {attributes}>{text}</span>"#)
+                })
+                .collect(),
+            CommentStyle::Html => self
+                .thread()
+                .map(|comment| {
+                    format!(
+                        r#"<span class="comment-end" id="{}"></span>"#,
+                        escape_html(&comment.id)
+                    )
+                })
+                .collect(),
+            CommentStyle::Footnotes if is_start => String::new(),
+            CommentStyle::Footnotes => format!("[^comment-{}]", self.id),
+        }
+    }
+
+    /// Render the comment and its replies as a footnote definition.
+    pub(crate) fn to_footnote(&self, doc: &MarkdownDocument, options: &MarkdownOptions) -> String {
+        let thread = self
+            .thread()
+            .map(|comment| comment.text(doc, options, "\n\n    "))
+            .collect::<Vec<_>>()
+            .join("\n\n    ");
+        format!("[^comment-{}]: {thread}\n", self.id)
+    }
+}
+
+/// Close comment ranges that do not end at the end of the paragraph they start in, and set the
+/// commented text of each comment.
+pub(crate) fn anchor_comments(markdown_doc: &mut MarkdownDocument) {
+    let ends: HashSet<String> = markdown_doc
+        .paragraphs()
+        .flat_map(|paragraph| paragraph.blocks.iter())
+        .filter(|block| block.text_type == TextType::CommentEnd)
+        .map(|block| block.text.clone())
+        .collect();
+    for paragraph in markdown_doc.paragraphs_mut() {
+        let unclosed: Vec<String> = paragraph
+            .blocks
+            .iter()
+            .filter(|block| {
+                block.text_type == TextType::CommentStart && !ends.contains(&block.text)
+            })
+            .map(|block| block.text.clone())
+            .collect();
+        for id in unclosed {
+            paragraph
+                .blocks
+                .push(TextBlock::new(id, None, TextType::CommentEnd));
+        }
+    }
+
+    let mut anchors: HashMap<String, String> = HashMap::new();
+    let mut open: Vec<String> = vec![];
+    for paragraph in markdown_doc.paragraphs() {
+        for id in &open {
+            anchors.entry(id.clone()).or_default().push('\n');
+        }
+        for block in &paragraph.blocks {
+            match block.text_type {
+                TextType::CommentStart => open.push(block.text.clone()),
+                TextType::CommentEnd => open.retain(|id| *id != block.text),
+                TextType::Text if RevisionMode::Accept.shows(block.revision.as_ref()) => {
+                    for id in &open {
+                        anchors.entry(id.clone()).or_default().push_str(&block.text);
+                    }
+                }
+                _ => (),
+            }
+        }
+    }
+
+    for comment in markdown_doc.comments.iter_mut() {
+        comment.anchor = anchors
+            .get(&comment.id)
+            .map(|anchor| anchor.trim().to_string())
+            .unwrap_or_default();
+        for reply in comment.replies.iter_mut() {
+            reply.anchor = match anchors.get(&reply.id) {
+                Some(anchor) => anchor.trim().to_string(),
+                None => comment.anchor.clone(),
+            };
+        }
+    }
+}
@@ -0,0 +1,83 @@
+use docx_rust::DocxError;
+use hard_xml::XmlError;
+use std::{error::Error, fmt, io};
+use zip::result::ZipError;
+
+/// Errors that can occur while parsing a DOCX file or serializing the result.
+#[derive(Debug)]
+pub enum DocxParserError {
+    /// Reading the input or writing the output failed.
+    Io(io::Error),
+    /// The input is not a valid zip archive.
+    Zip(ZipError),
+    /// One of the XML parts in the archive could not be parsed.
+    Xml(XmlError),
+    /// A part that every DOCX file must have, e.g. `word/document.xml`, is missing.
+    MissingPart(String),
+    /// The input is readable, but its content cannot be converted, e.g. an encrypted document.
+    Unsupported(String),
+    /// Serializing the document to JSON failed.
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for DocxParserError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocxParserError::Io(err) => write!(f, "I/O error: {err}"),
+            DocxParserError::Zip(err) => write!(f, "invalid DOCX archive: {err}"),
+            DocxParserError::Xml(err) => write!(f, "invalid XML: {err}"),
+            DocxParserError::MissingPart(part) => write!(f, "missing part: {part}"),
+            DocxParserError::Unsupported(reason) => write!(f, "unsupported content: {reason}"),
+            DocxParserError::Json(err) => write!(f, "JSON serialization failed: {err}"),
+        }
+    }
+}
+
+impl Error for DocxParserError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DocxParserError::Io(err) => Some(err),
+            DocxParserError::Zip(err) => Some(err),
+            DocxParserError::Xml(err) => Some(err),
+            DocxParserError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DocxParserError {
+    fn from(err: io::Error) -> Self {
+        DocxParserError::Io(err)
+    }
+}
+
+impl From<ZipError> for DocxParserError {
+    fn from(err: ZipError) -> Self {
+        match err {
+            ZipError::Io(err) => DocxParserError::Io(err),
+            err => DocxParserError::Zip(err),
+        }
+    }
+}
+
+impl From<XmlError> for DocxParserError {
+    fn from(err: XmlError) -> Self {
+        DocxParserError::Xml(err)
+    }
+}
+
+impl From<DocxError> for DocxParserError {
+    fn from(err: DocxError) -> Self {
+        match err {
+            DocxError::IO(err) => err.into(),
+            DocxError::Zip(err) => err.into(),
+            DocxError::Xml(err) => err.into(),
+        }
+    }
+}
+
+impl From<serde_json::Error> for DocxParserError {
+    fn from(err: serde_json::Error) -> Self {
+        DocxParserError::Json(err)
+    }
+}
@@ -6,28 +6,81 @@
 //! use docx_parser::MarkdownDocument;
 //!
 //! let markdown_doc = MarkdownDocument::from_file("./test/tables.docx");
-//! let markdown = markdown_doc.to_markdown(true);
+//! let markdown = markdown_doc.to_markdown(false);
 //! let json = markdown_doc.to_json(true);
 //! println!("\n\n{}", markdown);
 //! println!("\n\n{}", json);
 //! ```
+//!
+//! When the input cannot be trusted, use the fallible constructors instead:
+//!
+//! ```
+//! use docx_parser::{DocxParserError, MarkdownDocument};
+//!
+//! match MarkdownDocument::try_from_file("./test/does-not-exist.docx") {
+//!     Ok(markdown_doc) => println!("{}", markdown_doc.to_markdown(false)),
+//!     Err(DocxParserError::Io(err)) => eprintln!("Could not read file: {err}"),
+//!     Err(err) => eprintln!("Could not parse file: {err}"),
+//! }
+//! ```
 
+mod comments;
+mod error;
+mod notes;
+mod number_format;
+mod numbering;
+mod options;
+mod sdt;
+mod sections;
+mod style_map;
+mod styles;
+mod table_export;
+mod tables;
 mod utils;
-
-use docx_rust::document::BodyContent::{Paragraph, Sdt, SectionProperty, Table, TableCell};
-use docx_rust::document::{ParagraphContent, RunContent, TableCellContent, TableRowContent};
-use docx_rust::formatting::{NumberFormat, OnOffOnlyType, ParagraphProperty};
-use docx_rust::media::MediaType;
-use docx_rust::styles::StyleType;
-use docx_rust::DocxFile;
+mod wordml;
+
+pub use comments::Comment;
+pub use docx_rust::formatting::NumberFormat;
+pub use error::DocxParserError;
+pub use notes::{Note, NoteKind};
+pub use number_format::format_number;
+pub use numbering::{MarkdownNumbering, MarkdownNumberingLevel};
+pub use options::{
+    CommentStyle, FormattingStyle, HeaderFooterStyle, MarkdownOptions, MergedTableStyle,
+    RevisionMode, TableExportFormat, TableExportOptions, TableFormat, UnderlineStyle,
+};
+pub use sdt::{FieldValue, FormField, MarkdownSdt, SdtProperties, SdtType};
+pub use sections::{HeaderFooter, HeaderFooterKind, Section};
+pub use style_map::{ParagraphTarget, RunTarget, StyleMap};
+pub use tables::{Alignment, MarkdownTable, MarkdownTableCell, MarkdownTableRow};
+
+use docx_rust::document::{Break, BreakType, EndnoteReference, FootnoteReference, RunContent};
+use docx_rust::formatting::{
+    BorderStyle, CharacterProperty, HighlightType, JustificationVal, ParagraphProperty,
+    UnderlineStyle as UnderlineType, VertAlignType,
+};
+use docx_rust::media::{get_media_type, MediaType};
+use docx_rust::rels::Relationships;
+use docx_rust::{app::App, core::Core, styles::Styles};
+use hard_xml::XmlRead;
 use serde::Serialize;
-use serde_json;
-use std::collections::HashMap;
-use std::path::Path;
-use std::str::FromStr;
-use utils::{max_lengths_per_column, save_image_to_file, serialize_images, table_row_to_markdown};
-
-#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+use tables::{read_table, table_to_html, table_to_markdown, CellFormat};
+use utils::{
+    is_compound_file, longest_run, quote, save_image_to_file, serialize_images, yaml_string,
+};
+use wordml::BodyContent::{Paragraph, Sdt, SectionProperty, Table};
+use wordml::{ParagraphContent, Parts, Run};
+use zip::{result::ZipError, ZipArchive};
+
+/// Parts that every DOCX file must contain, checked before parsing so we can report which one is missing.
+const REQUIRED_PARTS: [&str; 3] = ["[Content_Types].xml", "_rels/.rels", "word/document.xml"];
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BlockStyle {
     /// Use bold
@@ -38,9 +91,63 @@ pub struct BlockStyle {
     pub underline: bool,
     /// Use strikethrough
     pub strike: bool,
+    /// Use inline code
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub code: bool,
+    /// Use superscript
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub superscript: bool,
+    /// Use subscript
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub subscript: bool,
+    /// The highlight color, e.g. `yellow`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight: Option<String>,
+    /// Use small capitals
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub small_caps: bool,
+    /// Show the text in capitals
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub caps: bool,
+    /// Hide the text
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub hidden: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     /// Size is specified in points x 2, so size 19 is equal to 9.5pt
     pub size: Option<isize>,
+    /// The font of the text, e.g. `Consolas`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<String>,
+    /// The color of the text as a hexadecimal RGB value, e.g. `FF0000`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// The id of the character style of the run, e.g. `Strong`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_style: Option<String>,
+    /// The toggles that are set explicitly, on or off, which the styles that this one is
+    /// combined with cannot change
+    #[serde(skip)]
+    explicit: Toggles,
+}
+
+/// Toggle properties of a run, such as bold, which a run can turn on or off.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+struct Toggles {
+    bold: bool,
+    italics: bool,
+    underline: bool,
+    strike: bool,
+    small_caps: bool,
+    caps: bool,
+    hidden: bool,
+}
+
+/// Combine a toggle with that of another style, unless it is set explicitly.
+fn combine_toggle(value: &mut bool, explicit: &mut bool, other: bool, other_explicit: bool) {
+    if !*explicit {
+        *value |= other;
+        *explicit = other_explicit;
+    }
 }
 
 impl BlockStyle {
@@ -50,33 +157,166 @@ impl BlockStyle {
             italics: false,
             underline: false,
             strike: false,
+            code: false,
+            superscript: false,
+            subscript: false,
+            highlight: None,
+            small_caps: false,
+            caps: false,
+            hidden: false,
             size: None,
+            font: None,
+            color: None,
+            character_style: None,
+            explicit: Toggles::default(),
         }
     }
 
+    /// Add the formatting of `other`, e.g. of the paragraph style to that of a run. The size of
+    /// this style and the toggles that it turns on or off explicitly take precedence.
     pub fn combine_with(&mut self, other: &BlockStyle) {
-        self.bold = other.bold;
-        self.italics = other.italics;
-        self.underline = other.underline;
-        self.strike = other.strike;
-        if let Some(size) = other.size {
-            self.size = Some(size);
+        let (explicit, other_explicit) = (&mut self.explicit, &other.explicit);
+        combine_toggle(
+            &mut self.bold,
+            &mut explicit.bold,
+            other.bold,
+            other_explicit.bold,
+        );
+        combine_toggle(
+            &mut self.italics,
+            &mut explicit.italics,
+            other.italics,
+            other_explicit.italics,
+        );
+        combine_toggle(
+            &mut self.underline,
+            &mut explicit.underline,
+            other.underline,
+            other_explicit.underline,
+        );
+        combine_toggle(
+            &mut self.strike,
+            &mut explicit.strike,
+            other.strike,
+            other_explicit.strike,
+        );
+        self.code |= other.code;
+        combine_toggle(
+            &mut self.small_caps,
+            &mut explicit.small_caps,
+            other.small_caps,
+            other_explicit.small_caps,
+        );
+        combine_toggle(
+            &mut self.caps,
+            &mut explicit.caps,
+            other.caps,
+            other_explicit.caps,
+        );
+        combine_toggle(
+            &mut self.hidden,
+            &mut explicit.hidden,
+            other.hidden,
+            other_explicit.hidden,
+        );
+        self.superscript |= other.superscript;
+        self.subscript |= other.subscript;
+        self.size = self.size.or(other.size);
+        if self.highlight.is_none() {
+            self.highlight = other.highlight.clone();
+        }
+        if self.font.is_none() {
+            self.font = other.font.clone();
+        }
+        if self.color.is_none() {
+            self.color = other.color.clone();
+        }
+        if self.character_style.is_none() {
+            self.character_style = other.character_style.clone();
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-#[serde(rename_all = "camelCase")]
-
-pub struct MarkdownNumbering {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub id: Option<isize>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub indent_level: Option<isize>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub format: Option<String>, // NumberFormat
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub level_text: Option<String>,
+impl<'a> From<&'a CharacterProperty<'a>> for BlockStyle {
+    fn from(character_property: &'a CharacterProperty) -> Self {
+        let mut block_style = BlockStyle::new();
+        if let Some(style_id) = &character_property.style_id {
+            block_style.character_style = Some(style_id.value.to_string());
+        }
+        if let Some(size) = &character_property.size {
+            block_style.size = Some(size.value);
+        }
+        if let Some(fonts) = &character_property.fonts {
+            block_style.font = fonts.ascii.clone().or_else(|| fonts.h_ansi.clone());
+            block_style.code = block_style
+                .font
+                .as_deref()
+                .is_some_and(styles::is_monospace_font);
+        }
+        // A toggle without a value is on, e.g. <w:b/>, and `false` or `0` turns it off
+        let is_on = |value: Option<bool>| value != Some(false);
+        if let Some(bold) = &character_property.bold {
+            block_style.bold = is_on(bold.value);
+            block_style.explicit.bold = true;
+        }
+        if let Some(underline) = &character_property.underline {
+            block_style.underline = !matches!(underline.val, Some(UnderlineType::None));
+            block_style.explicit.underline = true;
+        }
+        if let Some(italics) = &character_property.italics {
+            block_style.italics = is_on(italics.value);
+            block_style.explicit.italics = true;
+        }
+        let strikes = [
+            character_property
+                .strike
+                .as_ref()
+                .map(|strike| strike.value),
+            character_property
+                .dstrike
+                .as_ref()
+                .map(|dstrike| dstrike.value),
+        ];
+        if strikes.iter().any(Option::is_some) {
+            block_style.strike = strikes.into_iter().flatten().any(is_on);
+            block_style.explicit.strike = true;
+        }
+        match character_property
+            .vertical_align
+            .as_ref()
+            .and_then(|vertical_align| vertical_align.value.as_ref())
+        {
+            Some(VertAlignType::Superscript) => block_style.superscript = true,
+            Some(VertAlignType::Subscript) => block_style.subscript = true,
+            _ => (),
+        }
+        block_style.highlight = match character_property
+            .highlight
+            .as_ref()
+            .and_then(|highlight| highlight.value.as_ref())
+        {
+            None | Some(HighlightType::None) => None,
+            Some(color) => Some(color.to_string()),
+        };
+        if let Some(small_caps) = &character_property.small_caps {
+            block_style.small_caps = is_on(small_caps.value);
+            block_style.explicit.small_caps = true;
+        }
+        if let Some(caps) = &character_property.caps {
+            block_style.caps = is_on(caps.value);
+            block_style.explicit.caps = true;
+        }
+        if let Some(vanish) = &character_property.vanish {
+            block_style.hidden = is_on(vanish.value);
+            block_style.explicit.hidden = true;
+        }
+        block_style.color = character_property
+            .color
+            .as_ref()
+            .map(|color| color.value.to_string())
+            .filter(|color| color != "auto");
+        block_style
+    }
 }
 
 #[derive(Debug, Default, Clone, Serialize)]
@@ -84,12 +324,35 @@ pub struct MarkdownNumbering {
 pub struct ParagraphStyle {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style_id: Option<String>,
+    /// The name of the paragraph style, e.g. `heading 1`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The outline level, from 0 for a heading of level 1, where 9 is body text. Built-in
+    /// heading styles without one get that of their level.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub outline_lvl: Option<isize>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub numbering: Option<MarkdownNumbering>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_break_before: Option<bool>,
+    /// Paragraphs with this style are code, e.g. with the `Source Code` style or a monospace font
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub code_block: bool,
+    /// Paragraphs with this style are block quotes, e.g. with the `Quote` style
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub quote: bool,
+    /// The left indentation in twentieths of a point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indent: Option<isize>,
+    /// The hanging indentation of the lines after the first one, in twentieths of a point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hanging: Option<isize>,
+    /// Whether the paragraph has a bottom border, which makes an empty paragraph a horizontal rule
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub border_bottom: Option<bool>,
+    /// The horizontal alignment of the paragraph, where justified text has none
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alignment: Option<Alignment>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<BlockStyle>,
 }
@@ -98,17 +361,31 @@ impl ParagraphStyle {
     pub fn new() -> Self {
         ParagraphStyle {
             style_id: None,
+            name: None,
             outline_lvl: None,
             numbering: None,
             page_break_before: None,
+            code_block: false,
+            quote: false,
+            indent: None,
+            hanging: None,
+            border_bottom: None,
+            alignment: None,
             style: None,
         }
     }
 
     pub fn combine_with(&mut self, other: &ParagraphStyle) {
         self.style_id = self.style_id.clone().or_else(|| other.style_id.clone());
-        self.outline_lvl = self.outline_lvl.or_else(|| other.outline_lvl);
-        self.page_break_before = self.page_break_before.or_else(|| other.page_break_before);
+        self.name = self.name.clone().or_else(|| other.name.clone());
+        self.outline_lvl = self.outline_lvl.or(other.outline_lvl);
+        self.page_break_before = self.page_break_before.or(other.page_break_before);
+        self.code_block |= other.code_block;
+        self.quote |= other.quote;
+        self.indent = self.indent.or(other.indent);
+        self.hanging = self.hanging.or(other.hanging);
+        self.border_bottom = self.border_bottom.or(other.border_bottom);
+        self.alignment = self.alignment.or(other.alignment);
         if self.numbering.is_none() {
             self.numbering = other.numbering.clone()
         }
@@ -120,6 +397,29 @@ impl ParagraphStyle {
             self.style = other.style.clone();
         }
     }
+
+    /// The construct that the style map maps this style to, by its id or name.
+    pub fn target<'a>(&self, style_map: &'a StyleMap) -> Option<&'a ParagraphTarget> {
+        style_map.paragraph_target(self.style_id.as_deref(), self.name.as_deref())
+    }
+
+    /// The Markdown heading level of paragraphs with this style: the level that `heading_styles`
+    /// maps the name or id of the style to, where 0 is no heading, or else the outline level.
+    pub fn heading_level(&self, heading_styles: &HashMap<String, usize>) -> Option<usize> {
+        let mapped = heading_styles.iter().find(|(name, _)| {
+            let name = name.to_lowercase();
+            [&self.name, &self.style_id]
+                .into_iter()
+                .flatten()
+                .any(|style_name| style_name.to_lowercase() == name)
+        });
+        match (mapped, self.outline_lvl) {
+            (Some((_, 0)), _) => None,
+            (Some((_, level)), _) => Some(*level),
+            (None, Some(outline_lvl @ 0..=8)) => Some(outline_lvl as usize + 1),
+            (None, _) => None,
+        }
+    }
 }
 
 impl<'a> From<&'a ParagraphProperty<'a>> for ParagraphStyle {
@@ -135,44 +435,49 @@ impl<'a> From<&'a ParagraphProperty<'a>> for ParagraphStyle {
         if let Some(page_break_before) = &paragraph_property.page_break_before {
             paragraph_style.page_break_before = page_break_before.value;
         }
+        if let Some(indent) = &paragraph_property.indent {
+            paragraph_style.indent = indent.left;
+            paragraph_style.hanging = indent.hanging;
+        }
+        if let Some(bottom) = paragraph_property
+            .border
+            .as_ref()
+            .and_then(|border| border.bottom.as_ref())
+        {
+            paragraph_style.border_bottom = Some(!matches!(
+                bottom.style,
+                BorderStyle::Nil | BorderStyle::None
+            ));
+        }
+        if let Some(justification) = &paragraph_property.justification {
+            paragraph_style.alignment = match justification.value {
+                JustificationVal::Left | JustificationVal::Start => Some(Alignment::Left),
+                JustificationVal::Center => Some(Alignment::Center),
+                JustificationVal::Right | JustificationVal::End => Some(Alignment::Right),
+                JustificationVal::Both | JustificationVal::Distribute => None,
+            };
+        }
         if let Some(numbering) = &paragraph_property.numbering {
             paragraph_style.numbering = Some(MarkdownNumbering {
                 id: numbering.id.as_ref().map(|ni| ni.value),
                 indent_level: numbering.level.as_ref().map(|level| level.value),
                 format: None,
                 level_text: None,
+                levels: vec![],
             });
         }
-        if paragraph_property.r_pr.len() > 0 {
+        if !paragraph_property.r_pr.is_empty() {
             let mut block_style = BlockStyle::new();
-            paragraph_property
-                .r_pr
-                .iter()
-                .for_each(|character_property| {
-                    if let Some(size) = &character_property.size {
-                        block_style.size = Some(size.value);
-                    }
-                    if character_property.bold.is_some() {
-                        block_style.bold = true;
-                    }
-                    if character_property.underline.is_some() {
-                        block_style.underline = true;
-                    }
-                    if character_property.italics.is_some() || character_property.emphasis.is_some()
-                    {
-                        block_style.italics = true;
-                    }
-                    if character_property.strike.is_some() || character_property.dstrike.is_some() {
-                        block_style.strike = true;
-                    }
-                });
+            for character_property in &paragraph_property.r_pr {
+                block_style.combine_with(&character_property.into());
+            }
             paragraph_style.style = Some(block_style);
         }
         paragraph_style
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 pub enum TextType {
     Text,
     Image,
@@ -187,15 +492,84 @@ pub enum TextType {
     CodeBlock,
     HeaderBlock,
     BookmarkLink,
+    /// Start of a commented range, the text is the comment id
+    CommentStart,
+    /// End of a commented range, the text is the comment id
+    CommentEnd,
+    /// Reference to a footnote, the text is the note id
+    FootnoteReference,
+    /// Reference to an endnote, the text is the note id
+    EndnoteReference,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum RevisionKind {
+    Insertion,
+    Deletion,
+    MoveFrom,
+    MoveTo,
+}
+
+impl RevisionKind {
+    /// Whether the text is part of the final document, i.e. after accepting all changes.
+    pub fn is_added(&self) -> bool {
+        matches!(self, RevisionKind::Insertion | RevisionKind::MoveTo)
+    }
+
+    /// The CriticMarkup start and end tags.
+    fn critic_markup(&self) -> (&'static str, &'static str) {
+        if self.is_added() {
+            ("{++", "++}")
+        } else {
+            ("{--", "--}")
+        }
+    }
+}
+
+/// A tracked change
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Revision {
+    pub kind: RevisionKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+impl Revision {
+    fn new(kind: RevisionKind, author: &Option<Cow<str>>, date: &Option<Cow<str>>) -> Self {
+        Revision {
+            kind,
+            author: author.as_ref().map(|author| author.to_string()),
+            date: date.as_ref().map(|date| date.to_string()),
+        }
+    }
+}
+
+impl RevisionMode {
+    /// Whether a block with this revision is shown in this mode.
+    fn shows(&self, revision: Option<&Revision>) -> bool {
+        match (self, revision) {
+            (RevisionMode::Accept, Some(revision)) => revision.kind.is_added(),
+            (RevisionMode::Reject, Some(revision)) => !revision.kind.is_added(),
+            _ => true,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq, Eq, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TextBlock {
     pub text_type: TextType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<BlockStyle>,
     pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub revision: Option<Revision>,
+    /// The content control that contains the text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sdt: Option<SdtProperties>,
 }
 
 impl TextBlock {
@@ -204,50 +578,315 @@ impl TextBlock {
             style,
             text,
             text_type,
+            revision: None,
+            sdt: None,
         }
     }
 
     pub fn to_markdown(&self, paragraph_style: &ParagraphStyle) -> String {
-        let mut markdown = self.text.clone();
+        format_text(
+            &self.text,
+            &self.formatting(paragraph_style),
+            &MarkdownOptions::default(),
+        )
+    }
 
-        let mut style = if self.style.is_some() {
-            self.style.as_ref().unwrap().clone()
-        } else {
-            BlockStyle::new()
-        };
+    /// The formatting of the block in Markdown, including that of the paragraph style.
+    fn formatting(&self, paragraph_style: &ParagraphStyle) -> BlockStyle {
+        let mut style = self.style.clone().unwrap_or_default();
 
         if let Some(block_style) = &paragraph_style.style {
             style.combine_with(block_style);
         };
-
-        // Add bold formatting if enabled
-        if style.bold {
-            markdown = format!("**{markdown}**");
+        BlockStyle {
+            size: None,
+            font: None,
+            color: None,
+            character_style: None,
+            ..style
         }
+    }
 
-        // Add italic formatting if enabled
-        if style.italics {
-            markdown = format!("*{markdown}*");
+    /// The formatting of the block in Markdown, with the construct that the style map maps its
+    /// character style to, or `None` when the block is left out, like hidden text. A custom
+    /// character style that is kept as a span is returned by name in the `character_style` of
+    /// the formatting.
+    fn formatting_with_options(
+        &self,
+        paragraph_style: &ParagraphStyle,
+        doc: &MarkdownDocument,
+        options: &MarkdownOptions,
+    ) -> Option<BlockStyle> {
+        let mut formatting = self.formatting(paragraph_style);
+        if formatting.hidden && !options.hidden_text {
+            return None;
+        }
+        formatting.hidden = false;
+        if options.underline == UnderlineStyle::Ignore {
+            formatting.underline = false;
+        }
+        if options.formatting == FormattingStyle::None {
+            formatting = BlockStyle {
+                superscript: false,
+                subscript: false,
+                highlight: None,
+                small_caps: false,
+                caps: false,
+                ..formatting
+            };
+        }
+        if let Some(style) = self.style.as_ref().filter(|_| options.fonts_and_colors) {
+            // The font of code is implied by the backticks
+            formatting.font = style.font.clone().filter(|_| !formatting.code);
+            formatting.color = style.color.clone();
+        }
+        let Some(id) = self
+            .style
+            .as_ref()
+            .and_then(|style| style.character_style.as_deref())
+        else {
+            return Some(formatting);
+        };
+        let name = doc.character_style_names.get(id).map(String::as_str);
+        match options.style_map.run_target(Some(id), name) {
+            Some(RunTarget::Bold) => formatting.bold = true,
+            Some(RunTarget::Italics) => formatting.italics = true,
+            Some(RunTarget::Strike) => formatting.strike = true,
+            Some(RunTarget::Code) => formatting.code = true,
+            Some(RunTarget::Ignore) => return None,
+            None if options.custom_styles && doc.custom_styles.contains(id) => {
+                formatting.character_style = Some(name.unwrap_or(id).to_string());
+            }
+            None => (),
         }
+        Some(formatting)
+    }
+}
+
+/// Add the runs that are waiting for more runs to the Markdown, with line breaks as hard line
+/// breaks, or as spaces in inline code.
+fn flush_text(
+    markdown: &mut String,
+    pending_runs: &mut Vec<(BlockStyle, String)>,
+    options: &MarkdownOptions,
+) {
+    if !pending_runs.is_empty() {
+        *markdown += &format_runs(pending_runs, options).replace('\n', "\\\n");
+        pending_runs.clear();
+    }
+}
+
+/// Wrap text in the Markdown for its formatting.
+fn format_text(text: &str, style: &BlockStyle, options: &MarkdownOptions) -> String {
+    format_runs(&[(style.clone(), text.to_string())], options)
+}
 
-        // Add underline formatting if enabled
-        if style.underline {
-            markdown = format!("__{markdown}__");
+/// Wrap consecutive runs in the Markdown for their formatting. Formatting that several runs
+/// share wraps all of them, like pandoc does, e.g. `**bold *bold italics***`.
+fn format_runs(runs: &[(BlockStyle, String)], options: &MarkdownOptions) -> String {
+    let segments: Vec<Segment> = runs
+        .iter()
+        .map(|(style, text)| Segment {
+            markers: Marker::of(style),
+            code: style.code,
+            caps: style.caps,
+            text,
+        })
+        .collect();
+    format_segments(&segments, options)
+}
+
+/// Text with the markers that still have to be added around it.
+struct Segment<'a> {
+    markers: Vec<Marker>,
+    code: bool,
+    caps: bool,
+    text: &'a str,
+}
+
+fn format_segments(segments: &[Segment], options: &MarkdownOptions) -> String {
+    let mut markdown = String::new();
+    let mut i = 0;
+    while i < segments.len() {
+        let extent = |marker: &Marker| {
+            segments[i..]
+                .iter()
+                .take_while(|segment| segment.markers.contains(marker))
+                .count()
+        };
+        // The marker that spans the most segments wraps all of them, the outermost one first
+        let marker = segments[i]
+            .markers
+            .iter()
+            .map(|marker| (marker, extent(marker)))
+            .fold(None, |widest, (marker, count)| match widest {
+                Some((_, widest_count)) if widest_count >= count => widest,
+                _ => Some((marker, count)),
+            });
+        if let Some((marker, count)) = marker {
+            let inner: Vec<Segment> = segments[i..i + count]
+                .iter()
+                .map(|segment| Segment {
+                    markers: segment
+                        .markers
+                        .iter()
+                        .filter(|other| *other != marker)
+                        .cloned()
+                        .collect(),
+                    ..*segment
+                })
+                .collect();
+            markdown += &outside_whitespace(&format_segments(&inner, options), |inner| {
+                marker.wrap(inner, options)
+            });
+            i += count;
+        } else {
+            let leaf = &segments[i];
+            let count = segments[i..]
+                .iter()
+                .take_while(|segment| {
+                    segment.markers.is_empty()
+                        && segment.code == leaf.code
+                        && segment.caps == leaf.caps
+                })
+                .count();
+            let mut text: String = segments[i..i + count]
+                .iter()
+                .map(|segment| segment.text)
+                .collect();
+            if leaf.caps {
+                text = text.to_uppercase();
+            }
+            if leaf.code {
+                // Add inline code, with more backticks than the text contains in a row
+                markdown += &outside_whitespace(&text.replace('\n', " "), |code| {
+                    let backticks = "`".repeat(longest_run(code, '`') + 1);
+                    if code.starts_with('`') || code.ends_with('`') {
+                        format!("{backticks} {code} {backticks}")
+                    } else {
+                        format!("{backticks}{code}{backticks}")
+                    }
+                });
+            } else {
+                markdown += &text;
+            }
+            i += count;
         }
+    }
+    markdown
+}
+
+/// Wrap the Markdown, without its leading and trailing whitespace, which is kept outside the
+/// markers because they only apply when they are next to the text.
+fn outside_whitespace(markdown: &str, wrap: impl Fn(&str) -> String) -> String {
+    let trimmed = markdown.trim();
+    if trimmed.is_empty() {
+        return markdown.to_string();
+    }
+    let (leading, rest) = markdown.split_at(markdown.len() - markdown.trim_start().len());
+    let (_, trailing) = rest.split_at(trimmed.len());
+    format!("{leading}{}{trailing}", wrap(trimmed))
+}
+
+/// A formatting that wraps text in markers.
+#[derive(Debug, Clone, PartialEq)]
+enum Marker {
+    /// A custom character style that is kept as a pandoc span, by name
+    CustomStyle(String),
+    /// The font and color as CSS
+    Css(String),
+    SmallCaps,
+    Highlight,
+    Strike,
+    Underline,
+    Italics,
+    Bold,
+    Superscript,
+    Subscript,
+}
+
+impl Marker {
+    /// The markers of the formatting, from the outermost to the innermost.
+    fn of(style: &BlockStyle) -> Vec<Marker> {
+        let css: Vec<String> = [
+            style
+                .font
+                .as_ref()
+                .map(|font| format!("font-family: '{font}'")),
+            style.color.as_ref().map(|color| format!("color: #{color}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        [
+            style.character_style.clone().map(Marker::CustomStyle),
+            (!css.is_empty()).then(|| Marker::Css(css.join("; ").replace('"', ""))),
+            style.small_caps.then_some(Marker::SmallCaps),
+            style.highlight.is_some().then_some(Marker::Highlight),
+            style.strike.then_some(Marker::Strike),
+            style.underline.then_some(Marker::Underline),
+            style.italics.then_some(Marker::Italics),
+            style.bold.then_some(Marker::Bold),
+            style.superscript.then_some(Marker::Superscript),
+            style.subscript.then_some(Marker::Subscript),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
 
-        // Add strike-through formatting if enabled
-        if style.strike {
-            markdown = format!("~~{markdown}~~");
+    fn wrap(&self, markdown: &str, options: &MarkdownOptions) -> String {
+        let html = options.formatting == FormattingStyle::Html;
+        match self {
+            Marker::CustomStyle(name) => format!(
+                "[{markdown}]{{custom-style=\"{}\"}}",
+                name.replace('"', "\\\"")
+            ),
+            Marker::Css(css) if html => format!("<span style=\"{css}\">{markdown}</span>"),
+            Marker::Css(css) => format!("[{markdown}]{{style=\"{css}\"}}"),
+            Marker::SmallCaps if html => {
+                format!("<span style=\"font-variant: small-caps\">{markdown}</span>")
+            }
+            Marker::SmallCaps => format!("[{markdown}]{{.smallcaps}}"),
+            Marker::Highlight if html => format!("<mark>{markdown}</mark>"),
+            Marker::Highlight => format!("=={markdown}=="),
+            Marker::Strike => format!("~~{markdown}~~"),
+            Marker::Underline => match options.underline {
+                UnderlineStyle::Ignore => markdown.to_string(),
+                UnderlineStyle::Html => format!("<u>{markdown}</u>"),
+                UnderlineStyle::Pandoc => format!("[{markdown}]{{.underline}}"),
+            },
+            Marker::Italics => format!("*{markdown}*"),
+            Marker::Bold => format!("**{markdown}**"),
+            Marker::Superscript if html => format!("<sup>{markdown}</sup>"),
+            Marker::Subscript if html => format!("<sub>{markdown}</sub>"),
+            Marker::Superscript | Marker::Subscript => {
+                let marker = if *self == Marker::Superscript {
+                    "^"
+                } else {
+                    "~"
+                };
+                // Pandoc needs spaces to be escaped, except in code
+                if markdown.contains('`') {
+                    format!("{marker}{markdown}{marker}")
+                } else {
+                    format!("{marker}{}{marker}", markdown.replace(' ', "\\ "))
+                }
+            }
         }
-        markdown
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct MarkdownParagraph {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<ParagraphStyle>,
     pub blocks: Vec<TextBlock>,
+    /// The tracked change of the paragraph mark: an inserted mark split a paragraph, a deleted
+    /// one joins it with the next paragraph
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mark_revision: Option<Revision>,
 }
 
 impl MarkdownParagraph {
@@ -255,206 +894,624 @@ impl MarkdownParagraph {
         MarkdownParagraph {
             style: None,
             blocks: vec![],
+            mark_revision: None,
+        }
+    }
+
+    /// Whether the paragraph mark is a tracked change that this mode removes, which joins the
+    /// paragraph with the next one.
+    fn removes_mark(&self, mode: RevisionMode) -> bool {
+        self.mark_revision
+            .as_ref()
+            .is_some_and(|revision| !mode.shows(Some(revision)))
+    }
+
+    /// The effective style of the paragraph: its direct formatting on top of its paragraph style
+    /// (or the default paragraph style), which includes the styles it is based on.
+    pub fn effective_style(&self, doc: &MarkdownDocument) -> ParagraphStyle {
+        self.resolve_style(&doc.styles, doc.default_style.as_deref())
+    }
+
+    fn resolve_style(
+        &self,
+        styles: &HashMap<String, ParagraphStyle>,
+        default_style: Option<&str>,
+    ) -> ParagraphStyle {
+        let mut style = self.style.clone().unwrap_or_default();
+        let style_id = style.style_id.as_deref().or(default_style);
+        if let Some(doc_style) = style_id.and_then(|style_id| styles.get(style_id)) {
+            style.combine_with(doc_style);
         }
+        style
     }
 
     /// Convert a MarkdownParagraph to a Markdown string.
     pub fn to_markdown(
         &self,
         styles: &HashMap<String, ParagraphStyle>,
-        numberings: &mut HashMap<isize, usize>,
+        numberings: &mut HashMap<(isize, isize), usize>,
         doc: &MarkdownDocument,
+        options: &MarkdownOptions,
     ) -> String {
         let mut markdown = String::new();
 
-        let mut style = if self.style.is_some() {
-            self.style.as_ref().unwrap().clone()
-        } else {
-            ParagraphStyle::default()
-        };
+        if !self
+            .blocks
+            .iter()
+            .any(|block| options.revisions.shows(block.revision.as_ref()))
+        {
+            return markdown;
+        }
+
+        if self.is_horizontal_rule() {
+            return "---".to_string();
+        }
+
+        let mut style = self.resolve_style(styles, doc.default_style.as_deref());
 
-        if let Some(style_id) = &style.style_id {
-            if let Some(doc_style) = styles.get(style_id) {
-                style.combine_with(doc_style);
+        // The style map takes precedence over the built-in rules
+        let target = self.target(&style, doc, options);
+        match target {
+            Some(ParagraphTarget::Ignore) => return markdown,
+            Some(ParagraphTarget::CodeBlock) => {
+                return ParagraphTarget::CodeBlock.wrap(&self.plain_text(options.revisions))
             }
-            // markdown += &format!("[{}]", style_id);
-        };
+            Some(_) => (),
+            None => {
+                // Add the heading level if available, using the smallest heading level for higher levels
+                if let Some(heading_level) = style.heading_level(&options.heading_styles) {
+                    markdown += &format!("{} ", "#".repeat(heading_level.min(6)));
+                    // The heading already stands out like the bold or italics of its style do,
+                    // so only the run formatting of the paragraph itself is kept
+                    style.style = self.style.as_ref().and_then(|style| style.style.clone());
+                }
 
-        // Add outline level if available
-        if let Some(outline_lvl) = style.outline_lvl {
-            // Convert outline level to appropriate Markdown heading level
-            let heading_level = match outline_lvl {
-                0 => "# ",
-                1 => "## ",
-                2 => "### ",
-                3 => "#### ",
-                4 => "##### ",
-                _ => "###### ", // Use the smallest heading level for higher levels
-            };
-            markdown += heading_level;
+                // Add numbering if available
+                if let Some(numbering) = &style.numbering {
+                    match (
+                        numbering.id,
+                        doc.numberings.get(&numbering.id.unwrap_or_default()),
+                    ) {
+                        // A list id of 0 removes the numbering of the paragraph style
+                        (None | Some(0), _) => (),
+                        (Some(_), Some(doc_numbering)) => {
+                            let level = numbering.indent_level.unwrap_or_default();
+                            let marker = doc_numbering.next_marker(level, numberings);
+                            markdown += &format!("{marker} ");
+                        }
+                        (Some(id), None) => {
+                            let level = numbering.indent_level.unwrap_or_default();
+                            let count = numberings.entry((id, level)).or_insert(0);
+                            *count += 1;
+                            markdown += &format!("{count}. ");
+                        }
+                    }
+                }
+            }
         }
 
-        // Add numbering if available
-        if let Some(numbering) = &style.numbering {
-            if let Some(level) = numbering.indent_level {
-                if level > 0 {
-                    markdown += &"    ".repeat(level as usize); // Start numbering from 1
+        // Wrap consecutive blocks of the same tracked change in CriticMarkup, and consecutive
+        // blocks with the same formatting in a single pair of markers
+        let mut open_revision: Option<RevisionKind> = None;
+        let mut pending_runs: Vec<(BlockStyle, String)> = vec![];
+        let mut highlight: Option<String> = None;
+        for (i, block) in self.blocks.iter().enumerate() {
+            if !options.revisions.shows(block.revision.as_ref())
+                || block.text_type == TextType::HorizontalRule
+            {
+                continue;
+            }
+            if matches!(
+                block.text_type,
+                TextType::CommentStart | TextType::CommentEnd
+            ) {
+                // Replies are rendered together with the comment they reply to
+                if let Some(comment) = doc.comments.iter().find(|c| c.id == block.text) {
+                    flush_text(&mut markdown, &mut pending_runs, options);
+                    let marker =
+                        comment.marker_to_markdown(&self.blocks[i..], &mut highlight, doc, options);
+                    // The end of a highlight or a footnote reference goes before the spaces
+                    // that end the commented text
+                    let moves_before_spaces = block.text_type == TextType::CommentEnd
+                        && matches!(
+                            options.comments,
+                            CommentStyle::CriticMarkup | CommentStyle::Footnotes
+                        );
+                    if moves_before_spaces {
+                        let end = markdown.trim_end_matches([' ', '\t']).len();
+                        markdown.insert_str(end, &marker);
+                    } else {
+                        markdown += &marker;
+                    }
                 }
+                continue;
             }
-            if let Some(id) = numbering.id {
-                let format = match &doc.numberings[&id].format {
-                    Some(entry) => NumberFormat::from_str(entry).unwrap_or(NumberFormat::Decimal),
-                    None => NumberFormat::Decimal,
-                };
-                let count = numberings.entry(id).or_insert(0); // Start numbering from 1
-                let numbering_symbol = match format {
-                    NumberFormat::UpperRoman => format!("{}.", ((*count) as u8 + b'I') as char),
-                    NumberFormat::LowerRoman => format!("{}.", ((*count) as u8 + b'i') as char),
-                    NumberFormat::UpperLetter => format!("{}.", ((*count) as u8 + b'A') as char),
-                    NumberFormat::LowerLetter => format!("{}.", ((*count) as u8 + b'a') as char),
-                    NumberFormat::Bullet => match &doc.numberings[&id].level_text {
-                        Some(level_text) if level_text.trim().is_empty() => " ".to_string(),
-                        _ => "-".to_string(),
-                    },
-                    _ => format!("{}.", *count + 1),
-                };
-                *count += 1;
-                markdown += &format!("{numbering_symbol} ");
+            if let Some(kind) = NoteKind::of(block) {
+                let note = doc
+                    .notes
+                    .iter()
+                    .find(|note| note.kind == kind && note.id == block.text);
+                if let Some(number) = note.and_then(|note| note.number) {
+                    flush_text(&mut markdown, &mut pending_runs, options);
+                    markdown += &format!("[^{number}]");
+                }
+                continue;
             }
+            let revision = match options.revisions {
+                RevisionMode::All => block.revision.as_ref().map(|revision| revision.kind),
+                _ => None,
+            };
+            if revision != open_revision {
+                flush_text(&mut markdown, &mut pending_runs, options);
+                if let Some(kind) = open_revision {
+                    markdown += kind.critic_markup().1;
+                }
+                if let Some(kind) = revision {
+                    markdown += kind.critic_markup().0;
+                }
+                open_revision = revision;
+            }
+            let Some(formatting) = block.formatting_with_options(&style, doc, options) else {
+                continue;
+            };
+            match pending_runs.last_mut() {
+                Some((pending_formatting, text)) if *pending_formatting == formatting => {
+                    text.push_str(&block.text)
+                }
+                _ => pending_runs.push((formatting, block.text.clone())),
+            }
+        }
+        flush_text(&mut markdown, &mut pending_runs, options);
+        if let Some(kind) = open_revision {
+            markdown += kind.critic_markup().1;
+        }
+        // A line break at the end of a paragraph has no effect
+        while let Some(stripped) = markdown.strip_suffix("\\\n") {
+            markdown.truncate(stripped.len());
+        }
+        match target {
+            Some(target) => target.wrap(&markdown),
+            None => markdown,
+        }
+    }
+
+    /// The construct of the paragraph: the one that the style map maps its style to, or else a
+    /// pandoc div for a custom style that is kept.
+    fn target(
+        &self,
+        style: &ParagraphStyle,
+        doc: &MarkdownDocument,
+        options: &MarkdownOptions,
+    ) -> Option<ParagraphTarget> {
+        if self.is_horizontal_rule() {
+            return None;
+        }
+        if let Some(target) = style.target(&options.style_map) {
+            return Some(target.clone());
         }
+        if style.heading_level(&options.heading_styles).is_none()
+            && (style.code_block || self.is_code())
+        {
+            return Some(ParagraphTarget::CodeBlock);
+        }
+        let style_id = style.style_id.as_ref()?;
+        if !options.custom_styles
+            || !doc.custom_styles.contains(style_id)
+            || style.heading_level(&options.heading_styles).is_some()
+        {
+            return None;
+        }
+        let name = style.name.as_ref().unwrap_or(style_id);
+        Some(ParagraphTarget::CustomStyle(name.clone()))
+    }
+
+    /// The depth of the block quote that the paragraph is in: at least 1 for a quote style such
+    /// as `Quote`, or else 1 for each full half inch of left indentation beyond that of the default
+    /// paragraph style. Headings, list items and paragraphs that the style map converts or that
+    /// keep their custom style are not quoted.
+    fn quote_depth(
+        &self,
+        style: &ParagraphStyle,
+        target: Option<&ParagraphTarget>,
+        doc: &MarkdownDocument,
+        options: &MarkdownOptions,
+    ) -> usize {
+        let is_list_item = style
+            .numbering
+            .as_ref()
+            .is_some_and(|numbering| numbering.id.is_some_and(|id| id != 0));
+        if style.heading_level(&options.heading_styles).is_some()
+            || (is_list_item && !style.quote)
+            || target.is_some_and(|target| *target != ParagraphTarget::CodeBlock)
+        {
+            return 0;
+        }
+        let left = |style: &ParagraphStyle| style.indent.unwrap_or(0) - style.hanging.unwrap_or(0);
+        let default_indent = doc
+            .default_style
+            .as_ref()
+            .and_then(|default_style| doc.styles.get(default_style))
+            .map_or(0, left);
+        let depth = ((left(style) - default_indent) / 720).max(0) as usize;
+        if style.quote {
+            depth.max(1)
+        } else {
+            depth
+        }
+    }
+
+    /// The level of the list item that the paragraph renders as, if any. Headings and paragraphs
+    /// that the style map converts or that keep their custom style have no list marker.
+    fn list_level(
+        &self,
+        style: &ParagraphStyle,
+        target: Option<&ParagraphTarget>,
+        options: &MarkdownOptions,
+    ) -> Option<isize> {
+        if target.is_some()
+            || self.is_horizontal_rule()
+            || style.heading_level(&options.heading_styles).is_some()
+        {
+            return None;
+        }
+        let numbering = style.numbering.as_ref()?;
+        match numbering.id {
+            None | Some(0) => None,
+            Some(_) => Some(numbering.indent_level.unwrap_or_default()),
+        }
+    }
+
+    /// Whether the paragraph is a horizontal rule, without other text.
+    fn is_horizontal_rule(&self) -> bool {
+        self.blocks
+            .iter()
+            .any(|block| block.text_type == TextType::HorizontalRule)
+            && !self.has_text()
+    }
+
+    /// Whether the paragraph has text, a link or an image.
+    fn has_text(&self) -> bool {
+        self.blocks.iter().any(|block| match block.text_type {
+            TextType::Text => !block.text.trim().is_empty(),
+            TextType::Link | TextType::Image => true,
+            _ => false,
+        })
+    }
+
+    /// Whether the text of the paragraph is a thematic break that Word did not convert to a
+    /// border, i.e. three or more of `-`, `_` or `*` on their own, e.g. `***`.
+    fn is_thematic_break(&self) -> bool {
+        if self
+            .blocks
+            .iter()
+            .any(|block| matches!(block.text_type, TextType::Link | TextType::Image))
+        {
+            return false;
+        }
+        let text: Vec<char> = self
+            .blocks
+            .iter()
+            .filter(|block| block.text_type == TextType::Text)
+            .flat_map(|block| block.text.chars())
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        text.len() >= 3 && ['-', '_', '*'].contains(&text[0]) && text.iter().all(|c| *c == text[0])
+    }
+
+    /// Replace the text of the paragraph by a horizontal rule. Comment markers and bookmarks
+    /// are kept.
+    fn make_horizontal_rule(&mut self) {
+        let revision = self.blocks.first().and_then(|block| block.revision.clone());
+        self.blocks
+            .retain(|block| !matches!(block.text_type, TextType::Text | TextType::HorizontalRule));
+        let mut rule = TextBlock::new("---".to_string(), None, TextType::HorizontalRule);
+        rule.revision = revision;
+        self.blocks.push(rule);
+    }
 
+    /// Whether all text of the paragraph is in a monospace font. Like pandoc, text with a code
+    /// character style is inline code, also when it is all text of the paragraph.
+    fn is_code(&self) -> bool {
+        let mut has_code = false;
         for block in &self.blocks {
-            markdown += &block.to_markdown(&style);
+            match block.text_type {
+                TextType::Text if block.text.trim().is_empty() => (),
+                TextType::Text
+                    if block
+                        .style
+                        .as_ref()
+                        .is_some_and(|style| style.code && style.character_style.is_none()) =>
+                {
+                    has_code = true
+                }
+                TextType::Text | TextType::Link | TextType::Image => return false,
+                _ => (),
+            }
         }
-        markdown
+        has_code
+    }
+
+    /// Convert the paragraph to Markdown on a single line, to fit in a table cell. Code blocks
+    /// become inline code.
+    fn to_cell_markdown(
+        &self,
+        numberings: &mut HashMap<(isize, isize), usize>,
+        doc: &MarkdownDocument,
+        options: &MarkdownOptions,
+    ) -> String {
+        let style = self.effective_style(doc);
+        if self.target(&style, doc, options) == Some(ParagraphTarget::CodeBlock) {
+            let code = BlockStyle {
+                code: true,
+                ..BlockStyle::default()
+            };
+            return format_text(
+                &self.plain_text(options.revisions).replace('\n', " "),
+                &code,
+                options,
+            );
+        }
+        self.to_markdown(&doc.styles, numberings, doc, options)
+            .replace("\\\n", "<br/>")
+            .replace('\n', "<br/>")
+    }
+
+    /// The visible text of the paragraph, without formatting.
+    fn plain_text(&self, revisions: RevisionMode) -> String {
+        self.blocks
+            .iter()
+            .filter(|block| block.text_type == TextType::Text)
+            .filter(|block| revisions.shows(block.revision.as_ref()))
+            .map(|block| block.text.as_str())
+            .collect()
     }
 
-    /// Convert a docx::Paragraph to a MarkdownParagraph
-    fn from_paragraph(
-        paragraph: &docx_rust::document::Paragraph,
-        docx: &docx_rust::Docx,
+    /// Convert a wordml::Paragraph to a MarkdownParagraph
+    /// Links and images are resolved with the relationships of the part that contains the paragraph.
+    pub(crate) fn from_paragraph(
+        paragraph: &wordml::Paragraph,
+        rels: Option<&Relationships>,
     ) -> MarkdownParagraph {
         let mut markdown_paragraph = MarkdownParagraph::new();
         if let Some(paragraph_property) = &paragraph.property {
-            let paragraph_style: ParagraphStyle = paragraph_property.into();
+            let paragraph_style: ParagraphStyle = (&paragraph_property.to_docx()).into();
             markdown_paragraph.style = Some(paragraph_style);
+            if let Some(mark) = &paragraph_property.mark {
+                let revisions = [
+                    (
+                        RevisionKind::Insertion,
+                        mark.insertion.as_ref().map(|r| (&r.author, &r.date)),
+                    ),
+                    (
+                        RevisionKind::Deletion,
+                        mark.deletion.as_ref().map(|r| (&r.author, &r.date)),
+                    ),
+                    (
+                        RevisionKind::MoveFrom,
+                        mark.move_from.as_ref().map(|r| (&r.author, &r.date)),
+                    ),
+                    (
+                        RevisionKind::MoveTo,
+                        mark.move_to.as_ref().map(|r| (&r.author, &r.date)),
+                    ),
+                ];
+                markdown_paragraph.mark_revision =
+                    revisions.into_iter().find_map(|(kind, revision)| {
+                        revision.map(|(author, date)| Revision::new(kind, author, date))
+                    });
+            }
         }
-        for paragraph_content in &paragraph.content {
-            match paragraph_content {
-                ParagraphContent::Run(run) => {
-                    let block_style = match &run.property {
-                        Some(character_property) => {
-                            let mut block_style = BlockStyle::new();
-                            if let Some(size) = &character_property.size {
-                                block_style.size = Some(size.value);
-                            }
-                            if character_property.bold.is_some() {
-                                block_style.bold = true;
-                            }
-                            if character_property.underline.is_some() {
-                                block_style.underline = true;
-                            }
-                            if character_property.italics.is_some()
-                                || character_property.emphasis.is_some()
-                            {
-                                block_style.italics = true;
-                            }
-                            if character_property.strike.is_some()
-                                || character_property.dstrike.is_some()
-                            {
-                                block_style.strike = true;
-                            }
-                            Some(block_style)
-                        }
-                        None => None,
-                    };
+        markdown_paragraph.push_contents(&paragraph.content, rels, None, None);
+        markdown_paragraph
+    }
 
-                    let is_same_style = |style: &Option<BlockStyle>| style == &block_style;
-
-                    for run_content in &run.content {
-                        match run_content {
-                            RunContent::Text(text) => {
-                                let text = text.text.to_string();
-                                let mut could_extend_text = false;
-                                if let Some(prev_block) = markdown_paragraph.blocks.last_mut() {
-                                    if is_same_style(&prev_block.style)
-                                        && prev_block.text_type == TextType::Text
-                                    {
-                                        prev_block.text.push_str(&text);
-                                        could_extend_text = true
-                                    }
-                                };
-                                if !could_extend_text {
-                                    let text_block =
-                                        TextBlock::new(text, block_style.clone(), TextType::Text);
-                                    markdown_paragraph.blocks.push(text_block);
-                                }
-                            }
-                            RunContent::Drawing(drawing) => {
-                                if let Some(inline) = &drawing.inline {
-                                    if let Some(graphic) = &inline.graphic {
-                                        let id = graphic.data.pic.fill.blip.embed.to_string();
-                                        if let Some(relationships) = &docx.document_rels {
-                                            if let Some(target) = relationships.get_target(&id) {
-                                                let descr = match &inline.doc_property.descr {
-                                                    Some(descr) => descr.to_string(),
-                                                    None => "".to_string(),
-                                                };
-                                                let img_text =
-                                                    format!("![{}](./{})", descr, target);
-                                                let text_block =
-                                                    TextBlock::new(img_text, None, TextType::Image);
-                                                markdown_paragraph.blocks.push(text_block);
-                                            }
-                                        }
-                                    }
-                                }
-                            }
-                            _ => (),
-                        }
-                    }
+    /// Add the content of a paragraph, or of a tracked change, field, smart tag or content
+    /// control inside it, to the paragraph.
+    fn push_contents(
+        &mut self,
+        contents: &[ParagraphContent],
+        rels: Option<&Relationships>,
+        revision: Option<&Revision>,
+        sdt: Option<&SdtProperties>,
+    ) {
+        for paragraph_content in contents {
+            match paragraph_content {
+                ParagraphContent::Run(run) => self.push_run(run, rels, revision, sdt),
+                ParagraphContent::Insertion(insertion) => {
+                    let revision =
+                        Revision::new(RevisionKind::Insertion, &insertion.author, &insertion.date);
+                    self.push_contents(&insertion.content, rels, Some(&revision), sdt);
+                }
+                ParagraphContent::Deletion(deletion) => {
+                    let revision =
+                        Revision::new(RevisionKind::Deletion, &deletion.author, &deletion.date);
+                    self.push_contents(&deletion.content, rels, Some(&revision), sdt);
+                }
+                ParagraphContent::MoveFrom(move_from) => {
+                    let revision =
+                        Revision::new(RevisionKind::MoveFrom, &move_from.author, &move_from.date);
+                    self.push_contents(&move_from.content, rels, Some(&revision), sdt);
+                }
+                ParagraphContent::MoveTo(move_to) => {
+                    let revision =
+                        Revision::new(RevisionKind::MoveTo, &move_to.author, &move_to.date);
+                    self.push_contents(&move_to.content, rels, Some(&revision), sdt);
+                }
+                ParagraphContent::SimpleField(field) => {
+                    self.push_contents(&field.content, rels, revision, sdt)
+                }
+                ParagraphContent::SmartTag(smart_tag) => {
+                    self.push_contents(&smart_tag.content, rels, revision, sdt)
                 }
                 ParagraphContent::Link(link) => {
                     let descr = link.content.content.first();
                     let target = match &link.anchor {
-                        Some(anchor) => Some(format!("#{}", anchor.to_string())),
+                        Some(anchor) => Some(format!("#{}", anchor)),
                         None => match &link.id {
-                            Some(id) => match &docx.document_rels {
-                                Some(doc_relationships) => {
-                                    doc_relationships.relationships.iter().find_map(|r| {
-                                        if r.id == *id {
-                                            Some(r.target.to_string())
-                                        } else {
-                                            None
-                                        }
-                                    })
-                                }
-                                None => None,
-                            },
+                            Some(id) => rels
+                                .and_then(|rels| rels.get_target(id))
+                                .map(|target| target.to_string()),
                             None => None,
                         },
                     };
                     if let (Some(RunContent::Text(descr)), Some(target)) = (descr, target) {
-                        let link = format!("[{}]({})", descr.text, target);
-                        let text_block = TextBlock::new(link, None, TextType::Link);
-                        markdown_paragraph.blocks.push(text_block);
+                        let link = if descr.text == target {
+                            format!("<{}>", target)
+                        } else {
+                            format!("[{}]({})", descr.text, target)
+                        };
+                        let mut text_block = TextBlock::new(link, None, TextType::Link);
+                        text_block.revision = revision.cloned();
+                        text_block.sdt = sdt.cloned();
+                        self.blocks.push(text_block);
                     }
                 }
+                ParagraphContent::CommentRangeStart(comment_range_start) => {
+                    let id = comment_range_start.id.to_string();
+                    let text_block = TextBlock::new(id, None, TextType::CommentStart);
+                    self.blocks.push(text_block);
+                }
+                ParagraphContent::CommentRangeEnd(comment_range_end) => {
+                    let id = comment_range_end.id.to_string();
+                    let text_block = TextBlock::new(id, None, TextType::CommentEnd);
+                    self.blocks.push(text_block);
+                }
                 ParagraphContent::BookmarkStart(bookmark_start) => {
                     if let Some(name) = &bookmark_start.name {
                         let bookmark = format!(r#"<a name="{}"></a>"#, name);
                         let text_block = TextBlock::new(bookmark, None, TextType::BookmarkLink);
-                        markdown_paragraph.blocks.push(text_block);
+                        self.blocks.push(text_block);
                     }
                 }
-                _ => (),
+                ParagraphContent::Sdt(inline_sdt) => {
+                    let properties = SdtProperties::from(&inline_sdt.property);
+                    self.push_contents(
+                        &inline_sdt.content.content,
+                        rels,
+                        revision,
+                        Some(&properties),
+                    );
+                }
             }
         }
-        markdown_paragraph
+    }
+
+    /// Add the content of a run, which may be part of a tracked change or content control, to
+    /// the paragraph.
+    fn push_run(
+        &mut self,
+        run: &Run,
+        rels: Option<&Relationships>,
+        revision: Option<&Revision>,
+        sdt: Option<&SdtProperties>,
+    ) {
+        let block_style = run.property.as_ref().map(BlockStyle::from);
+
+        let can_extend = |block: &TextBlock| {
+            block.style == block_style
+                && block.text_type == TextType::Text
+                && block.revision.as_ref() == revision
+                && block.sdt.as_ref() == sdt
+        };
+
+        for run_content in &run.content {
+            let text = match run_content {
+                RunContent::Text(text) => Some(text.text.as_ref()),
+                RunContent::DelText(text) => Some(text.text.as_ref()),
+                RunContent::Break(Break {
+                    ty: None | Some(BreakType::TextWrapping),
+                }) => Some("\n"),
+                RunContent::Tab(_) => Some("\t"),
+                _ => None,
+            };
+            if let Some(text) = text {
+                match self.blocks.last_mut() {
+                    Some(prev_block) if can_extend(prev_block) => prev_block.text.push_str(text),
+                    _ => {
+                        let mut text_block =
+                            TextBlock::new(text.to_string(), block_style.clone(), TextType::Text);
+                        text_block.revision = revision.cloned();
+                        text_block.sdt = sdt.cloned();
+                        self.blocks.push(text_block);
+                    }
+                }
+            } else if let RunContent::FootnoteReference(FootnoteReference {
+                id: Some(id), ..
+            }) = run_content
+            {
+                let mut text_block =
+                    TextBlock::new(id.to_string(), None, TextType::FootnoteReference);
+                text_block.revision = revision.cloned();
+                self.blocks.push(text_block);
+            } else if let RunContent::EndnoteReference(EndnoteReference { id: Some(id), .. }) =
+                run_content
+            {
+                let mut text_block =
+                    TextBlock::new(id.to_string(), None, TextType::EndnoteReference);
+                text_block.revision = revision.cloned();
+                self.blocks.push(text_block);
+            } else if let RunContent::Drawing(drawing) = run_content {
+                if let Some(inline) = &drawing.inline {
+                    if let Some(graphic) = &inline.graphic {
+                        let id = graphic.data.pic.fill.blip.embed.to_string();
+                        if let Some(relationships) = rels {
+                            if let Some(target) = relationships.get_target(&id) {
+                                let descr = match &inline.doc_property.descr {
+                                    Some(descr) => descr.to_string(),
+                                    None => "".to_string(),
+                                };
+                                let img_text = format!("![{}](./{})", descr, target);
+                                let mut text_block =
+                                    TextBlock::new(img_text, None, TextType::Image);
+                                text_block.revision = revision.cloned();
+                                text_block.sdt = sdt.cloned();
+                                self.blocks.push(text_block);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        for pict in &run.picts {
+            if pict.is_horizontal_rule() {
+                let mut text_block =
+                    TextBlock::new("---".to_string(), None, TextType::HorizontalRule);
+                text_block.revision = revision.cloned();
+                text_block.sdt = sdt.cloned();
+                self.blocks.push(text_block);
+            }
+        }
+    }
+}
+
+/// A paragraph converted to a Markdown block, which the next paragraph may join.
+struct ParagraphBlock {
+    target: Option<ParagraphTarget>,
+    /// The depth of the block quote
+    depth: usize,
+    /// The Markdown of the block before this paragraph, when the paragraph continues a quote
+    prefix: String,
+    /// The Markdown of the paragraph, or of the consecutive paragraphs with the same construct
+    markdown: String,
+}
+
+impl ParagraphBlock {
+    /// Join the next paragraph when it has the same construct and quote depth.
+    fn join(&self, next: &ParagraphBlock) -> Option<String> {
+        match &next.target {
+            Some(target) if self.target.as_ref() == Some(target) && self.depth == next.depth => {
+                target.join(&self.markdown, &next.markdown)
+            }
+            _ => None,
+        }
+    }
+
+    fn to_block(&self) -> String {
+        format!("{}{}\n", self.prefix, quote(&self.markdown, self.depth))
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Default, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MarkdownDocument {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -472,10 +1529,30 @@ pub struct MarkdownDocument {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub keywords: Option<String>,
     pub content: Vec<MarkdownContent>,
+    /// The paragraph styles, including the formatting of the styles they are based on and of the
+    /// document defaults
     pub styles: HashMap<String, ParagraphStyle>,
+    /// The id of the style of paragraphs without a style
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_style: Option<String>,
+    /// The character styles, including the formatting of the styles they are based on
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub character_styles: HashMap<String, BlockStyle>,
+    /// The names of the character styles, by their id
+    #[serde(skip)]
+    pub character_style_names: HashMap<String, String>,
+    /// The ids of the styles that the document defines, rather than being built into Word
+    #[serde(skip)]
+    pub custom_styles: HashSet<String>,
     pub numberings: HashMap<isize, MarkdownNumbering>,
     #[serde(serialize_with = "serialize_images")]
     pub images: HashMap<String, Vec<u8>>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<Comment>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<Note>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub sections: Vec<Section>,
 }
 
 impl MarkdownDocument {
@@ -490,30 +1567,77 @@ impl MarkdownDocument {
             keywords: None,
             content: vec![],
             styles: HashMap::new(),
+            default_style: None,
+            character_styles: HashMap::new(),
+            character_style_names: HashMap::new(),
+            custom_styles: HashSet::new(),
             numberings: HashMap::new(),
             images: HashMap::new(),
+            comments: vec![],
+            notes: vec![],
+            sections: vec![],
         }
     }
 
+    /// Parse a DOCX file.
+    ///
+    /// Panics when the file cannot be read or parsed, use [`MarkdownDocument::try_from_file`]
+    /// to handle those errors yourself.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Self {
-        let mut markdown_doc = MarkdownDocument::new();
+        match Self::try_from_file(path) {
+            Ok(markdown_doc) => markdown_doc,
+            Err(err) => panic!("Error processing file: {}", err),
+        }
+    }
 
-        let docx = match DocxFile::from_file(path) {
-            Ok(docx_file) => docx_file,
-            Err(err) => {
-                panic!("Error processing file: {:?}", err)
+    /// Parse a DOCX file, returning an error when it cannot be read or parsed.
+    pub fn try_from_file<P: AsRef<Path>>(path: P) -> Result<Self, DocxParserError> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Parse a DOCX file that is already in memory, e.g. the body of an HTTP request.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DocxParserError> {
+        Self::from_reader(Cursor::new(bytes))
+    }
+
+    /// Parse a DOCX file from any seekable reader.
+    pub fn from_reader<R: Read + Seek>(mut reader: R) -> Result<Self, DocxParserError> {
+        if is_compound_file(&mut reader)? {
+            return Err(DocxParserError::Unsupported(
+                "encrypted or legacy binary (.doc) Word document".to_string(),
+            ));
+        }
+
+        let mut archive = ZipArchive::new(reader)?;
+        for part in REQUIRED_PARTS {
+            match archive.by_name(part) {
+                Ok(_) => (),
+                Err(ZipError::FileNotFound) => {
+                    return Err(DocxParserError::MissingPart(part.to_string()))
+                }
+                Err(err) => return Err(err.into()),
             }
-        };
-        let docx = match docx.parse() {
-            Ok(docx) => docx,
-            Err(err) => {
-                panic!("Exiting: {:?}", err);
+        }
+        let parts = Parts::read(&mut archive)?;
+        Self::from_parts(&parts)
+    }
+
+    fn from_parts(parts: &Parts) -> Result<Self, DocxParserError> {
+        let document = match parts.get("word/document.xml") {
+            Some(document) => wordml::Document::from_str(document)?,
+            None => {
+                return Err(DocxParserError::MissingPart(
+                    "word/document.xml".to_string(),
+                ))
             }
         };
+        let mut markdown_doc = MarkdownDocument::new();
 
-        // println!("{:?}", &docx);
-
-        if let Some(app) = &docx.app {
+        let app = parts
+            .get("docProps/app.xml")
+            .map(App::from_str)
+            .transpose()?;
+        if let Some(app) = &app {
             if let Some(company) = &app.company {
                 if !company.is_empty() {
                     markdown_doc.company = Some(company.to_string());
@@ -521,7 +1645,11 @@ impl MarkdownDocument {
             }
         }
 
-        if let Some(core) = &docx.core {
+        let core = parts
+            .get("docProps/core.xml")
+            .map(Core::from_str)
+            .transpose()?;
+        if let Some(core) = &core {
             if let Some(title) = &core.title {
                 if !title.is_empty() {
                     markdown_doc.title = Some(title.to_string());
@@ -554,240 +1682,538 @@ impl MarkdownDocument {
             }
         }
 
-        if let Some(numbering) = &docx.numbering {
-            numbering.numberings.iter().for_each(|n| {
-                if let Some(id) = n.num_id {
-                    if let Some(details) = numbering.numbering_details(id) {
-                        markdown_doc.numberings.insert(
-                            id,
-                            MarkdownNumbering {
-                                id: Some(id),
-                                indent_level: None,
-                                format: details.levels[0]
-                                    .number_format
-                                    .as_ref()
-                                    .map(|i| i.value.to_string()),
-                                level_text: details.levels[0]
-                                    .level_text
-                                    .as_ref()
-                                    .map(|i| i.value.to_string()),
-                            },
-                        );
-                        ()
-                    }
-                }
-            })
-        }
-
-        for (id, (MediaType::Image, media_data)) in &docx.media {
-            markdown_doc.images.insert(id.clone(), media_data.to_vec());
-        }
+        markdown_doc.numberings = MarkdownNumbering::read_all(parts)?;
 
-        for style in &docx.styles.styles {
-            match style.ty {
-                Some(StyleType::Paragraph) => {
-                    if let Some(paragraph_property) = &style.paragraph {
-                        let paragraph_style: ParagraphStyle = paragraph_property.into();
-                        markdown_doc
-                            .styles
-                            .insert(style.style_id.to_string(), paragraph_style);
-                    }
-                }
-                _ => (),
+        for (id, media_data) in parts.media() {
+            if matches!(get_media_type(id), Some(MediaType::Image)) {
+                markdown_doc
+                    .images
+                    .insert(id.to_string(), media_data.to_vec());
             }
         }
 
-        for content in &docx.document.body.content {
-            match content {
-                Paragraph(paragraph) => {
-                    let markdown_paragraph = MarkdownParagraph::from_paragraph(&paragraph, &docx);
-                    if markdown_paragraph.blocks.len() > 0 {
-                        markdown_doc
-                            .content
-                            .push(MarkdownContent::Paragraph(markdown_paragraph));
-                    }
-                }
-                Table(table) => {
-                    let rows_columns: MarkdownTable = table
-                        .rows
-                        .iter()
-                        .map(|row| {
-                            let is_header = match &row.property.table_header {
-                                Some(table_header) => match table_header.value {
-                                    Some(OnOffOnlyType::On) => true,
-                                    _ => false,
-                                },
-                                None => false,
-                            };
-                            let cells: Vec<Vec<MarkdownParagraph>> = row
-                                .cells
-                                .iter()
-                                .filter_map(|row_content| match row_content {
-                                    TableRowContent::TableCell(cell) => {
-                                        let cells: Vec<MarkdownParagraph> = cell
-                                            .content
-                                            .iter()
-                                            .filter_map(|content| match content {
-                                                TableCellContent::Paragraph(paragraph) => {
-                                                    Some(MarkdownParagraph::from_paragraph(
-                                                        &paragraph, &docx,
-                                                    ))
-                                                } // _ => None,
-                                            })
-                                            .collect();
-                                        if cells.len() > 0 {
-                                            Some(cells)
-                                        } else {
-                                            None
-                                        }
-                                    }
-                                    _ => None,
-                                })
-                                .collect();
-                            MarkdownTableRow { is_header, cells }
-                        })
-                        .collect();
-
-                    markdown_doc
-                        .content
-                        .push(MarkdownContent::Table(rows_columns));
-                }
-                Sdt(_) => {
-                    // println!("Sdt");
-                }
-                SectionProperty(_sp) => {
-                    // println!("SectionProperty: {:?}", sp);
-                }
-                TableCell(tc) => {
-                    println!("TableCell: {:?}", tc);
+        let styles = parts
+            .get("word/styles.xml")
+            .map(Styles::from_str)
+            .transpose()?;
+        styles::read_styles(&styles.unwrap_or_default(), &mut markdown_doc);
+
+        let rels = parts.relationships("word/document.xml")?;
+        let rels = rels.as_ref();
+        markdown_doc.content =
+            MarkdownContent::from_body(&document.body.content, rels, &markdown_doc);
+        markdown_doc.sections =
+            Section::read_all(&document.body.content, parts, rels, &markdown_doc)?;
+
+        markdown_doc.comments = Comment::read_all(parts)?;
+        comments::anchor_comments(&mut markdown_doc);
+        markdown_doc.notes = Note::read_all(parts)?;
+        notes::number_notes(&mut markdown_doc);
+        styles::apply_character_styles(&mut markdown_doc);
+
+        Ok(markdown_doc)
+    }
+
+    /// All paragraphs in reading order, including the ones in tables and content controls.
+    pub(crate) fn paragraphs(&self) -> impl Iterator<Item = &MarkdownParagraph> {
+        self.content.iter().flat_map(MarkdownContent::paragraphs)
+    }
+
+    /// All paragraphs in reading order, including the ones in tables and content controls.
+    pub(crate) fn paragraphs_mut(&mut self) -> impl Iterator<Item = &mut MarkdownParagraph> {
+        self.content
+            .iter_mut()
+            .flat_map(MarkdownContent::paragraphs_mut)
+    }
+
+    /// The form fields of the document, i.e. the named content controls that hold text, a list
+    /// item, a date or a checkbox, in reading order.
+    pub fn form_fields(&self) -> Vec<FormField> {
+        let mut fields = vec![];
+        FormField::collect(&self.content, &mut fields);
+        fields
+    }
+
+    /// Serialize the form fields to a JSON object that maps the tag (or else the alias) of each
+    /// field to its type and value.
+    pub fn form_fields_to_json(&self, pretty: bool) -> Result<String, DocxParserError> {
+        let fields = self.form_fields();
+        let json = if pretty {
+            serde_json::to_string_pretty(&sdt::FormFieldMap(&fields))?
+        } else {
+            serde_json::to_string(&sdt::FormFieldMap(&fields))?
+        };
+        Ok(json)
+    }
+
+    /// The tables of the document in reading order, including the ones in content controls.
+    /// Nested tables are part of the cells that hold them.
+    pub fn tables(&self) -> Vec<&MarkdownTable> {
+        fn collect<'a>(content: &'a [MarkdownContent], tables: &mut Vec<&'a MarkdownTable>) {
+            for content in content {
+                match content {
+                    MarkdownContent::Paragraph(_) => {}
+                    MarkdownContent::Table(table) => tables.push(table),
+                    MarkdownContent::Sdt(sdt) => collect(&sdt.content, tables),
                 }
             }
         }
 
-        markdown_doc
+        let mut tables = vec![];
+        collect(&self.content, &mut tables);
+        tables
+    }
+
+    /// Convert the data in each table of the document to CSV, TSV or JSON records, with the
+    /// text of merged cells repeated in each column and row that they span.
+    pub fn tables_to_strings(
+        &self,
+        options: &TableExportOptions,
+    ) -> Result<Vec<String>, DocxParserError> {
+        self.tables()
+            .into_iter()
+            .map(|table| table_export::export_table(table, options))
+            .collect()
     }
 
+    /// Write the data in each table of the document to a file in the directory, e.g.
+    /// `table-1.csv`, creating the directory when needed, and return the paths of the files.
+    pub fn export_tables<P: AsRef<Path>>(
+        &self,
+        dir: P,
+        options: &TableExportOptions,
+    ) -> Result<Vec<PathBuf>, DocxParserError> {
+        std::fs::create_dir_all(&dir)?;
+        let mut paths = vec![];
+        for (index, export) in self.tables_to_strings(options)?.into_iter().enumerate() {
+            let path = dir.as_ref().join(format!(
+                "table-{}.{}",
+                index + 1,
+                options.format.extension()
+            ));
+            std::fs::write(&path, export)?;
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+
+    /// Serialize the document to JSON.
+    ///
+    /// Panics when serialization fails, use [`MarkdownDocument::try_to_json`] to handle that yourself.
     pub fn to_json(&self, pretty: bool) -> String {
-        if pretty {
-            serde_json::to_string_pretty(self).expect("Serialization failed")
+        self.try_to_json(pretty).expect("Serialization failed")
+    }
+
+    /// Serialize the document to JSON, returning an error when serialization fails.
+    pub fn try_to_json(&self, pretty: bool) -> Result<String, DocxParserError> {
+        let json = if pretty {
+            serde_json::to_string_pretty(self)?
         } else {
-            serde_json::to_string(self).expect("Serialization failed")
+            serde_json::to_string(self)?
+        };
+        Ok(json)
+    }
+
+    /// Save all images of the document, relative to the current working directory,
+    /// at the path they are referenced by in the Markdown output.
+    pub fn export_images(&self) -> Result<(), DocxParserError> {
+        for (image, data) in &self.images {
+            save_image_to_file(image, data)?;
         }
+        Ok(())
     }
 
+    /// Convert the document to Markdown, optionally exporting its images.
+    ///
+    /// Errors during the image export are ignored, use [`MarkdownDocument::try_to_markdown`] to
+    /// handle them yourself.
     pub fn to_markdown(&self, export_images: bool) -> String {
+        if export_images {
+            let _ = self.export_images();
+        }
+        self.to_markdown_with_options(&MarkdownOptions::default())
+    }
+
+    /// Convert the document to Markdown, optionally exporting its images, and return an error
+    /// when the image export fails.
+    pub fn try_to_markdown(&self, export_images: bool) -> Result<String, DocxParserError> {
+        if export_images {
+            self.export_images()?;
+        }
+        Ok(self.to_markdown_with_options(&MarkdownOptions::default()))
+    }
+
+    /// Convert the document to Markdown, without exporting its images.
+    pub fn to_markdown_with_options(&self, options: &MarkdownOptions) -> String {
         let mut markdown = String::new();
 
-        if let Some(title) = &self.title {
+        let header = self.sections.first().and_then(|section| {
+            section
+                .header(HeaderFooterKind::Default)
+                .or_else(|| section.header(HeaderFooterKind::First))
+        });
+        let footer = self.sections.last().and_then(|section| {
+            section
+                .footer(HeaderFooterKind::Default)
+                .or_else(|| section.footer(HeaderFooterKind::First))
+        });
+        let header_footer_blocks = |header_footer: Option<&HeaderFooter>| match header_footer {
+            Some(header_footer) => {
+                self.content_to_blocks(&header_footer.content, &mut HashMap::new(), options)
+            }
+            None => vec![],
+        };
+
+        match options.headers_footers {
+            HeaderFooterStyle::None => (),
+            HeaderFooterStyle::Blocks => {
+                for block in header_footer_blocks(header) {
+                    markdown += &format!("{block}\n");
+                }
+            }
+            HeaderFooterStyle::FrontMatter => {
+                let mut front_matter = String::new();
+                for (key, header_footer) in [("header", header), ("footer", footer)] {
+                    let text = header_footer_blocks(header_footer).join("\n");
+                    if !text.trim().is_empty() {
+                        front_matter += &format!("{key}: {}\n", yaml_string(text.trim()));
+                    }
+                }
+                if !front_matter.is_empty() {
+                    markdown += &format!("---\n{front_matter}---\n\n");
+                }
+            }
+        }
+
+        // A heading with the Title style shows the title already
+        let has_title_heading = self.paragraphs().any(|paragraph| {
+            let style = paragraph.effective_style(self);
+            style.heading_level(&options.heading_styles).is_some()
+                && [&style.style_id, &style.name]
+                    .into_iter()
+                    .flatten()
+                    .any(|name| styles::is_title_style(name))
+        });
+        if let Some(title) = self.title.as_ref().filter(|_| !has_title_heading) {
             markdown += &format!("# {}\n\n", title);
         }
 
-        let mut numberings: HashMap<isize, usize> = HashMap::new();
+        let mut numberings: HashMap<(isize, isize), usize> = HashMap::new();
+        let mut blocks = self.content_to_blocks(&self.content, &mut numberings, options);
+
+        let mut notes: Vec<&Note> = self.notes.iter().collect();
+        notes.sort_by_key(|note| note.number);
+        for note in notes {
+            if let Some(note) = note.to_markdown(self, options) {
+                blocks.push(note);
+            }
+        }
+
+        if options.comments == CommentStyle::Footnotes {
+            let referenced: Vec<&str> = self
+                .paragraphs()
+                .flat_map(|paragraph| paragraph.blocks.iter())
+                .filter(|block| block.text_type == TextType::CommentEnd)
+                .map(|block| block.text.as_str())
+                .collect();
+            for comment in &self.comments {
+                if referenced.contains(&comment.id.as_str()) {
+                    blocks.push(comment.to_footnote(self, options));
+                }
+            }
+        }
+
+        if options.headers_footers == HeaderFooterStyle::Blocks {
+            blocks.extend(header_footer_blocks(footer));
+        }
+
+        markdown += &blocks.join("\n");
+        markdown
+    }
 
-        for (index, content) in self.content.iter().enumerate() {
+    /// Render content, like the document body or a header, as Markdown blocks.
+    fn content_to_blocks(
+        &self,
+        content: &[MarkdownContent],
+        numberings: &mut HashMap<(isize, isize), usize>,
+        options: &MarkdownOptions,
+    ) -> Vec<String> {
+        let mut blocks: Vec<String> = vec![];
+        // The last block, when it is a paragraph, to join the next one with
+        let mut last_paragraph: Option<ParagraphBlock> = None;
+        // The levels of the list items that the next list item can be nested in
+        let mut list_levels: Vec<isize> = vec![];
+
+        for content in MarkdownContent::join_paragraphs(content, options.revisions).iter() {
             match content {
                 MarkdownContent::Paragraph(paragraph) => {
-                    markdown += &paragraph.to_markdown(&self.styles, &mut numberings, &self);
-                    markdown += "\n";
+                    let paragraph_as_markdown =
+                        paragraph.to_markdown(&self.styles, numberings, self, options);
+                    // Paragraphs that only contain hidden tracked changes are left out
+                    if paragraph_as_markdown.is_empty() {
+                        continue;
+                    }
+                    let style = paragraph.effective_style(self);
+                    let target = paragraph.target(&style, self, options);
+                    let mut paragraph_as_markdown = paragraph_as_markdown;
+                    match paragraph.list_level(&style, target.as_ref(), options) {
+                        // Nest the item only in the items of lower levels right before it, as a
+                        // deeper indentation without a parent item would make it a code block
+                        Some(level) => {
+                            list_levels.retain(|other| *other < level);
+                            paragraph_as_markdown.insert_str(0, &"    ".repeat(list_levels.len()));
+                            list_levels.push(level);
+                        }
+                        None => list_levels.clear(),
+                    }
+                    let mut paragraph_block = ParagraphBlock {
+                        depth: paragraph.quote_depth(&style, target.as_ref(), self, options),
+                        target,
+                        prefix: String::new(),
+                        markdown: paragraph_as_markdown,
+                    };
+                    if let Some(last) = last_paragraph.take() {
+                        if let Some(joined) = last.join(&paragraph_block) {
+                            paragraph_block.prefix = last.prefix;
+                            paragraph_block.markdown = joined;
+                            blocks.pop();
+                        } else if paragraph_block.depth > 0 && last.depth > 0 {
+                            // Continue the block quote, separated by an empty quoted line
+                            let depth = paragraph_block.depth.min(last.depth);
+                            paragraph_block.prefix =
+                                format!("{}{}\n", last.to_block(), vec![">"; depth].join(" "));
+                            blocks.pop();
+                        }
+                    }
+                    blocks.push(paragraph_block.to_block());
+                    last_paragraph = Some(paragraph_block);
+                    continue;
                 }
+                MarkdownContent::Table(_) if options.table_format == TableFormat::Skip => {}
                 MarkdownContent::Table(table) => {
-                    let table_with_simple_cells: Vec<(bool, Vec<String>)> = table
-                        .iter()
-                        .map(|MarkdownTableRow { is_header, cells }| {
-                            let row_content: &Vec<String> = &cells
-                                .iter()
-                                .map(|cell| {
-                                    let cell_content = &cell.iter().enumerate().fold(
-                                        "".to_string(),
-                                        |mut content, (i, paragraph)| {
-                                            let paragraph_as_markdown = &paragraph.to_markdown(
-                                                &self.styles,
-                                                &mut numberings,
-                                                &self,
-                                            );
-                                            if i + 1 < cell.len() {
-                                                content +=
-                                                    &format!("{}<br/>", paragraph_as_markdown);
-                                            } else {
-                                                content += paragraph_as_markdown;
-                                            }
-                                            content
-                                        },
-                                    );
-                                    cell_content.clone()
-                                })
-                                .collect();
-                            (is_header.clone(), row_content.clone())
-                        })
-                        .collect();
-                    let column_lengths = max_lengths_per_column(&table_with_simple_cells, 3);
-                    let divider = &table_row_to_markdown(
-                        &column_lengths,
-                        &column_lengths.iter().map(|i| "-".repeat(*i)).collect(),
-                    );
-                    let table = &table_with_simple_cells.iter().enumerate().fold(
-                        "".to_string(),
-                        |mut acc, (i, (is_header, row))| {
-                            let markdown_row = &table_row_to_markdown(&column_lengths, row);
-                            if i == 0 {
-                                if *is_header {
-                                    acc.push_str(markdown_row);
-                                    acc.push_str(divider);
-                                } else {
-                                    acc.push_str(&table_row_to_markdown(
-                                        &column_lengths,
-                                        &column_lengths.iter().map(|_| "".to_string()).collect(),
-                                    ));
-                                    acc.push_str(divider);
-                                    acc.push_str(markdown_row);
-                                }
-                            } else {
-                                acc.push_str(markdown_row);
-                            }
-                            if i == table_with_simple_cells.len() {
-                                acc.push_str("\n");
-                            }
-                            acc
+                    blocks.push(table_to_markdown(
+                        table,
+                        |cell, format| {
+                            self.cell_to_markdown(&cell.content, format, numberings, options)
                         },
-                    );
-                    markdown += table;
+                        options,
+                    ));
+                }
+                MarkdownContent::Sdt(sdt) => {
+                    blocks.extend(self.content_to_blocks(&sdt.content, numberings, options));
                 }
             };
-            if index != self.content.len() - 1 {
-                markdown += "\n";
+            last_paragraph = None;
+            list_levels.clear();
+        }
+
+        blocks
+    }
+
+    /// Render the content of a table cell: each paragraph on a single line, with nested tables
+    /// in HTML, or as Markdown blocks.
+    fn cell_to_markdown(
+        &self,
+        content: &[MarkdownContent],
+        format: CellFormat,
+        numberings: &mut HashMap<(isize, isize), usize>,
+        options: &MarkdownOptions,
+    ) -> Vec<String> {
+        if format == CellFormat::Blocks {
+            return self.content_to_blocks(content, numberings, options);
+        }
+        let mut markdown = vec![];
+        for content in MarkdownContent::join_paragraphs(content, options.revisions).iter() {
+            match content {
+                MarkdownContent::Paragraph(paragraph) => {
+                    markdown.push(paragraph.to_cell_markdown(numberings, self, options))
+                }
+                MarkdownContent::Table(table) => {
+                    markdown.push(table_to_html(table, |cell, format| {
+                        self.cell_to_markdown(&cell.content, format, numberings, options)
+                    }))
+                }
+                MarkdownContent::Sdt(sdt) => markdown.extend(self.cell_to_markdown(
+                    &sdt.content,
+                    format,
+                    numberings,
+                    options,
+                )),
             }
         }
+        markdown
+    }
 
-        if export_images {
-            for (image, data) in &self.images {
-                match save_image_to_file(image, data) {
-                    Ok(_) => (),
-                    Err(err) => eprintln!("{err}"),
-                };
+    /// Accept or reject all tracked changes, removing the hidden text from the document.
+    /// In [`RevisionMode::All`], the document is left unchanged.
+    pub fn apply_revisions(&mut self, mode: RevisionMode) {
+        if mode == RevisionMode::All {
+            return;
+        }
+        fn join(content: &mut Vec<MarkdownContent>, mode: RevisionMode) {
+            if let Cow::Owned(joined) = MarkdownContent::join_paragraphs(content, mode) {
+                *content = joined;
+            }
+            for content in content {
+                match content {
+                    MarkdownContent::Paragraph(_) => (),
+                    MarkdownContent::Table(table) => table
+                        .iter_mut()
+                        .flat_map(|row| row.cells.iter_mut())
+                        .for_each(|cell| join(&mut cell.content, mode)),
+                    MarkdownContent::Sdt(sdt) => join(&mut sdt.content, mode),
+                }
             }
         }
+        join(&mut self.content, mode);
+        for paragraph in self.paragraphs_mut() {
+            paragraph
+                .blocks
+                .retain(|block| mode.shows(block.revision.as_ref()));
+            paragraph
+                .blocks
+                .iter_mut()
+                .for_each(|block| block.revision = None);
+            paragraph.mark_revision = None;
+        }
+        self.content.retain(|content| match content {
+            MarkdownContent::Paragraph(paragraph) => !paragraph.blocks.is_empty(),
+            MarkdownContent::Table(_) | MarkdownContent::Sdt(_) => true,
+        });
+    }
+}
 
-        markdown
+impl TryFrom<&Path> for MarkdownDocument {
+    type Error = DocxParserError;
+
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        Self::try_from_file(path)
+    }
+}
+
+impl TryFrom<&[u8]> for MarkdownDocument {
+    type Error = DocxParserError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::from_bytes(bytes)
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
+#[allow(clippy::large_enum_variant)]
 pub enum MarkdownContent {
     Paragraph(MarkdownParagraph),
     Table(MarkdownTable),
+    Sdt(MarkdownSdt),
 }
 
-pub type MarkdownTable = Vec<MarkdownTableRow>;
+impl MarkdownContent {
+    fn paragraphs(&self) -> Box<dyn Iterator<Item = &MarkdownParagraph> + '_> {
+        match self {
+            MarkdownContent::Paragraph(paragraph) => Box::new(std::iter::once(paragraph)),
+            MarkdownContent::Table(table) => Box::new(
+                table
+                    .iter()
+                    .flat_map(|row| row.cells.iter())
+                    .flat_map(|cell| cell.content.iter())
+                    .flat_map(MarkdownContent::paragraphs),
+            ),
+            MarkdownContent::Sdt(sdt) => {
+                Box::new(sdt.content.iter().flat_map(MarkdownContent::paragraphs))
+            }
+        }
+    }
 
-#[derive(Debug, Serialize)]
-#[serde(rename_all = "camelCase")]
-pub struct MarkdownTableRow {
-    is_header: bool,
-    cells: Vec<MarkdownTableCell>,
-}
+    /// Join each paragraph whose paragraph mark is removed in this mode, i.e. a deleted mark when
+    /// accepting the changes or an inserted one when rejecting them, with the paragraph after it.
+    /// Like in Word, the joined paragraph has the properties of the mark that is left, i.e. those
+    /// of the paragraph after it.
+    fn join_paragraphs(content: &[MarkdownContent], mode: RevisionMode) -> Cow<'_, [Self]> {
+        let removes_mark = |content: &MarkdownContent| matches!(content, MarkdownContent::Paragraph(paragraph) if paragraph.removes_mark(mode));
+        if !content.iter().any(removes_mark) {
+            return Cow::Borrowed(content);
+        }
+        let mut joined: Vec<MarkdownContent> = vec![];
+        for content in content {
+            if let (Some(MarkdownContent::Paragraph(last)), MarkdownContent::Paragraph(next)) =
+                (joined.last_mut(), content)
+            {
+                if last.removes_mark(mode) {
+                    let mut blocks = std::mem::take(&mut last.blocks);
+                    blocks.extend(next.blocks.iter().cloned());
+                    *last = MarkdownParagraph {
+                        blocks,
+                        ..next.clone()
+                    };
+                    continue;
+                }
+            }
+            joined.push(content.clone());
+        }
+        Cow::Owned(joined)
+    }
 
-pub type MarkdownTableCell = Vec<MarkdownParagraph>;
+    fn paragraphs_mut(&mut self) -> Box<dyn Iterator<Item = &mut MarkdownParagraph> + '_> {
+        match self {
+            MarkdownContent::Paragraph(paragraph) => Box::new(std::iter::once(paragraph)),
+            MarkdownContent::Table(table) => Box::new(
+                table
+                    .iter_mut()
+                    .flat_map(|row| row.cells.iter_mut())
+                    .flat_map(|cell| cell.content.iter_mut())
+                    .flat_map(MarkdownContent::paragraphs_mut),
+            ),
+            MarkdownContent::Sdt(sdt) => Box::new(
+                sdt.content
+                    .iter_mut()
+                    .flat_map(MarkdownContent::paragraphs_mut),
+            ),
+        }
+    }
+
+    /// Convert the content of the body, a header, a footer or a table cell. Empty paragraphs are
+    /// left out, unless they have a bottom border, which makes them a horizontal rule. Links and
+    /// images are resolved with the relationships of the part that contains the content.
+    fn from_body(
+        content: &[wordml::BodyContent],
+        rels: Option<&Relationships>,
+        doc: &MarkdownDocument,
+    ) -> Vec<Self> {
+        content
+            .iter()
+            .filter_map(|content| match content {
+                Paragraph(paragraph) => {
+                    let mut markdown_paragraph = MarkdownParagraph::from_paragraph(paragraph, rels);
+                    let style = markdown_paragraph.effective_style(doc);
+                    let is_border =
+                        style.border_bottom == Some(true) && !markdown_paragraph.has_text();
+                    // Lines of dashes in code are code
+                    let is_break = !style.code_block
+                        && !markdown_paragraph.is_code()
+                        && markdown_paragraph.is_thematic_break();
+                    if is_border || is_break {
+                        markdown_paragraph.make_horizontal_rule();
+                    }
+                    if !markdown_paragraph.blocks.is_empty() {
+                        Some(MarkdownContent::Paragraph(markdown_paragraph))
+                    } else {
+                        None
+                    }
+                }
+                Table(table) => Some(MarkdownContent::Table(read_table(table, rels, doc))),
+                Sdt(sdt) => Some(MarkdownContent::Sdt(MarkdownSdt {
+                    properties: (&sdt.property).into(),
+                    content: Self::from_body(&sdt.content.content, rels, doc),
+                })),
+                SectionProperty(_sp) => {
+                    // println!("SectionProperty: {:?}", sp);
+                    None
+                }
+            })
+            .collect()
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -812,6 +2238,370 @@ mod tests {
         assert_eq!(markdown_pandoc, markdown);
     }
 
+    #[test]
+    fn test_lists_continuing_and_restarting() {
+        let markdown_doc = MarkdownDocument::from_file("./test/lists_continuing.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert!(markdown.starts_with("1. Foo\n\n2. Bar\n\n3. Baz\n\nInterruption.\n\n4. Bop\n"));
+
+        let markdown_doc = MarkdownDocument::from_file("./test/lists_restarting.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert!(markdown.starts_with("2. Foo\n\n3. Bar\n\n4. Baz\n\n> Interruption\n\n1. Bop.\n"));
+    }
+
+    #[test]
+    fn test_lists_without_parent_items() {
+        let markdown_doc = MarkdownDocument::from_file("./test/lists_multiple_initial.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!("a. foo\n\nb. bar\n\n- foo\n\n- bar\n", markdown);
+    }
+
+    #[test]
+    fn test_multi_level_numbering() {
+        let numbering = r#"<w:numbering xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:abstractNum w:abstractNumId="1"><w:nsid w:val="00000001"/><w:multiLevelType w:val="multilevel"/>
+                <w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/></w:lvl>
+                <w:lvl w:ilvl="1"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1.%2."/></w:lvl>
+                <w:lvl w:ilvl="2"><w:start w:val="1"/><w:numFmt w:val="lowerLetter"/><w:isLgl/><w:lvlText w:val="%1.%2.%3"/></w:lvl>
+            </w:abstractNum>
+            <w:num w:numId="1"><w:abstractNumId w:val="1"/></w:num>
+            <w:num w:numId="2"><w:abstractNumId w:val="1"/>
+                <w:lvlOverride w:ilvl="0"><w:startOverride w:val="5"/></w:lvlOverride>
+                <w:lvlOverride w:ilvl="1"><w:lvl w:ilvl="1"><w:start w:val="1"/><w:numFmt w:val="upperLetter"/><w:lvlRestart w:val="0"/><w:lvlText w:val="%1.%2"/></w:lvl></w:lvlOverride>
+            </w:num>
+        </w:numbering>"#;
+        let items = [
+            (1, 0, "One"),
+            (1, 1, "One one"),
+            (1, 1, "One two"),
+            (1, 2, "Legal"),
+            (1, 2, "Legal too"),
+            (1, 0, "Two"),
+            (1, 1, "Two one"),
+            (2, 0, "Five"),
+            (2, 1, "Five A"),
+            (2, 0, "Six"),
+            (2, 1, "Six B"),
+        ];
+        let paragraphs: String = items
+            .iter()
+            .map(|(id, level, text)| {
+                format!(
+                    r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="{level}"/><w:numId w:val="{id}"/></w:numPr></w:pPr><w:r><w:t>{text}</w:t></w:r></w:p>"#
+                )
+            })
+            .collect();
+        let document = format!(
+            r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{paragraphs}</w:body></w:document>"#
+        );
+        let bytes = fixture_with_parts(
+            "./test/lists.docx",
+            &[
+                ("word/numbering.xml", numbering),
+                ("word/document.xml", &document),
+            ],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            concat!(
+                "1. One\n\n",
+                "    1. One one\n\n",
+                "    2. One two\n\n",
+                "        a. Legal\n\n",
+                "        b. Legal too\n\n",
+                "2. Two\n\n",
+                "    1. Two one\n\n",
+                "5. Five\n\n",
+                "    A.  Five A\n\n",
+                "6. Six\n\n",
+                "    B.  Six B\n"
+            ),
+            markdown_doc.to_markdown(false)
+        );
+
+        let level = markdown_doc.numberings[&2].level(1).unwrap();
+        assert_eq!(Some("upperLetter"), level.format.as_deref());
+        assert_eq!(Some(0), level.restart);
+        assert_eq!(5, markdown_doc.numberings[&2].level(0).unwrap().start);
+    }
+
+    #[test]
+    fn test_style_inheritance() {
+        let styles = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:docDefaults><w:pPrDefault><w:pPr><w:pageBreakBefore w:val="false"/></w:pPr></w:pPrDefault></w:docDefaults>
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:pPr><w:outlineLvl w:val="0"/></w:pPr></w:style>
+            <w:style w:type="paragraph" w:styleId="MyHeading"><w:name w:val="My Heading"/><w:basedOn w:val="Heading1"/></w:style>
+            <w:style w:type="paragraph" w:styleId="MySubheading"><w:name w:val="My Subheading"/><w:basedOn w:val="MyHeading"/><w:pPr><w:outlineLvl w:val="1"/></w:pPr></w:style>
+        </w:styles>"#;
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:pStyle w:val="MyHeading"/></w:pPr><w:r><w:t>Title</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="MySubheading"/></w:pPr><w:r><w:t>Subtitle</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Text</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[("word/styles.xml", styles), ("word/document.xml", document)],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "# Title\n\n## Subtitle\n\nText\n",
+            markdown_doc.to_markdown(false)
+        );
+
+        assert_eq!(Some(0), markdown_doc.styles["MyHeading"].outline_lvl);
+        assert_eq!(
+            Some(false),
+            markdown_doc.styles["MyHeading"].page_break_before
+        );
+        let paragraphs: Vec<_> = markdown_doc.paragraphs().collect();
+        let style = paragraphs[1].effective_style(&markdown_doc);
+        assert_eq!(Some("MySubheading"), style.style_id.as_deref());
+        assert_eq!(Some(1), style.outline_lvl);
+        let style = paragraphs[2].effective_style(&markdown_doc);
+        assert_eq!(Some("Normal"), style.style_id.as_deref());
+        assert_eq!(None, style.outline_lvl);
+    }
+
+    #[test]
+    fn test_heading_styles() {
+        let styles = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:style w:type="paragraph" w:default="1" w:styleId="Standaard"><w:name w:val="Normal"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Titel"><w:name w:val="Titel"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Kop1"><w:name w:val="Kop 1"/></w:style>
+            <w:style w:type="paragraph" w:styleId="berschrift2"><w:name w:val="heading 2"/></w:style>
+            <w:style w:type="paragraph" w:styleId="MyHeading"><w:name w:val="My Heading"/><w:basedOn w:val="berschrift2"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Chapter"><w:name w:val="Chapter"/></w:style>
+            <w:style w:type="paragraph" w:styleId="TOCHeading"><w:name w:val="TOC Heading"/></w:style>
+        </w:styles>"#;
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:pStyle w:val="Titel"/></w:pPr><w:r><w:t>Title</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Chapter"/></w:pPr><w:r><w:t>Chapter</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Kop1"/></w:pPr><w:r><w:t>Kop</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="berschrift2"/></w:pPr><w:r><w:t>Überschrift</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="MyHeading"/></w:pPr><w:r><w:t>Based on a heading</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="TOCHeading"/></w:pPr><w:r><w:t>Contents</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[("word/styles.xml", styles), ("word/document.xml", document)],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "# Title\n\nChapter\n\n# Kop\n\n## Überschrift\n\n## Based on a heading\n\nContents\n",
+            markdown_doc.to_markdown(false)
+        );
+
+        let options = MarkdownOptions {
+            heading_styles: HashMap::from([
+                ("chapter".to_string(), 1),
+                ("Kop 1".to_string(), 2),
+                ("Titel".to_string(), 0),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            "Title\n\n# Chapter\n\n## Kop\n\n## Überschrift\n\n## Based on a heading\n\nContents\n",
+            markdown_doc.to_markdown_with_options(&options)
+        );
+    }
+
+    #[test]
+    fn test_title_heading_and_core_title() {
+        let markdown_doc = MarkdownDocument::from_file("./test/document-properties.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert!(markdown.starts_with("# Testing custom properties\n\n"));
+        assert_eq!(1, markdown.matches("# Testing custom properties").count());
+
+        // When the Title style is not a heading, the title from the core properties is the heading
+        let options = MarkdownOptions {
+            heading_styles: HashMap::from([("Title".to_string(), 0)]),
+            ..Default::default()
+        };
+        let markdown = markdown_doc.to_markdown_with_options(&options);
+        assert!(
+            markdown.starts_with("# Testing custom properties\n\nTesting custom properties\n\n")
+        );
+    }
+
+    #[test]
+    fn test_style_map() {
+        let styles = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+            <w:style w:type="paragraph" w:styleId="NoteBox"><w:name w:val="Note Box"/></w:style>
+            <w:style w:type="paragraph" w:styleId="CodeSample"><w:name w:val="Code Sample"/></w:style>
+            <w:style w:type="paragraph" w:styleId="QuoteLarge"><w:name w:val="Quote Large"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Draft"><w:name w:val="Draft"/></w:style>
+            <w:style w:type="character" w:styleId="CodeChar"><w:name w:val="Code Char"/></w:style>
+        </w:styles>"#;
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Not a heading</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="NoteBox"/></w:pPr><w:r><w:t>Mind the gap.</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Draft"/></w:pPr><w:r><w:t>Left out</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="CodeSample"/></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t>fn main() {</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="CodeSample"/></w:pPr><w:r><w:t>}</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="QuoteLarge"/></w:pPr><w:r><w:t>To be</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="QuoteLarge"/></w:pPr><w:r><w:t>or not to be</w:t></w:r></w:p>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Run </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="CodeChar"/></w:rPr><w:t>cargo `test`</w:t></w:r>
+            </w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[("word/styles.xml", styles), ("word/document.xml", document)],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        let style_map: StyleMap = "
+            p[style-name='heading 1'] => p
+            p[style-name='Note Box'] => admonition:note
+            p[style-name='Code Sample'] => pre
+            p.QuoteLarge => blockquote
+            p[style-name='Draft'] => !
+            r[style-name='Code Char'] => code
+        "
+        .parse()
+        .unwrap();
+        let options = MarkdownOptions {
+            style_map,
+            ..Default::default()
+        };
+        assert_eq!(
+            "Not a heading\n\n> [!NOTE]\n> Mind the gap.\n\n```\nfn main() {\n}\n```\n\n> To be\n>\n> or not to be\n\nRun `` cargo `test` ``\n",
+            markdown_doc.to_markdown_with_options(&options)
+        );
+
+        let style_map = StyleMap::new().paragraph("Quote Large", ParagraphTarget::BulletList);
+        let options = MarkdownOptions {
+            style_map,
+            ..Default::default()
+        };
+        assert!(markdown_doc
+            .to_markdown_with_options(&options)
+            .contains("- To be\n- or not to be\n"));
+    }
+
+    #[test]
+    fn test_custom_styles() {
+        let markdown_doc = MarkdownDocument::from_file("./test/custom_style_reference.docx");
+        let options = MarkdownOptions {
+            custom_styles: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            "::: {custom-style=\"First Paragraph\"}\nThis is a test of custom-styles.\n:::\n\n\
+            Here is something [*emphasized*]{custom-style=\"Emphatic\"}. \
+            And here is something [**strong**]{custom-style=\"Strengthened\"}.\n\n\
+            ::: {custom-style=\"My Block Style\"}\nOne paragraph of text.\n\n\
+            And another paragraph of [*really cool*]{custom-style=\"Emphatic\"} text.\n:::\n",
+            markdown_doc.to_markdown_with_options(&options)
+        );
+
+        // Mapped styles are not kept
+        let options = MarkdownOptions {
+            custom_styles: true,
+            style_map: StyleMap::new().paragraph("My Block Style", ParagraphTarget::BlockQuote),
+            ..Default::default()
+        };
+        assert!(markdown_doc
+            .to_markdown_with_options(&options)
+            .contains("> One paragraph of text.\n>\n> And another paragraph"));
+    }
+
+    #[test]
+    fn test_character_styles() {
+        let styles = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+            <w:style w:type="character" w:styleId="Strong"><w:name w:val="Strong"/><w:rPr><w:b/></w:rPr></w:style>
+            <w:style w:type="character" w:styleId="Emphasis"><w:name w:val="Emphasis"/><w:rPr><w:i/></w:rPr></w:style>
+            <w:style w:type="character" w:styleId="StrongEmphasis"><w:name w:val="Strong Emphasis"/><w:basedOn w:val="Strong"/><w:rPr><w:i/></w:rPr></w:style>
+        </w:styles>"#;
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">Some </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Strong"/></w:rPr><w:t>strong</w:t></w:r>
+                <w:r><w:t xml:space="preserve">, </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Emphasis"/></w:rPr><w:t>emphasized</w:t></w:r>
+                <w:r><w:t xml:space="preserve"> and </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="StrongEmphasis"/></w:rPr><w:t>both</w:t></w:r>
+                <w:r><w:t xml:space="preserve"> text</w:t></w:r>
+            </w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[("word/styles.xml", styles), ("word/document.xml", document)],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "Some **strong**, *emphasized* and ***both*** text\n",
+            markdown_doc.to_markdown(false)
+        );
+
+        let style = &markdown_doc.character_styles["StrongEmphasis"];
+        assert!(style.bold && style.italics);
+        assert_eq!(Some("StrongEmphasis"), style.character_style.as_deref());
+    }
+
+    #[test]
+    fn test_run_formatting_of_paragraph_styles() {
+        let styles = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:docDefaults><w:rPrDefault><w:rPr><w:b/></w:rPr></w:rPrDefault></w:docDefaults>
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Note"><w:name w:val="Note"/><w:link w:val="NoteChar"/></w:style>
+            <w:style w:type="character" w:styleId="NoteChar"><w:name w:val="Note Char"/><w:link w:val="Note"/><w:rPr><w:i/></w:rPr></w:style>
+            <w:style w:type="paragraph" w:styleId="Aside"><w:name w:val="Aside"/><w:rPr><w:i/><w:b w:val="0"/></w:rPr></w:style>
+            <w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:rPr><w:i/></w:rPr></w:style>
+        </w:styles>"#;
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Heading</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Bold by default</w:t></w:r></w:p>
+            <w:p><w:r><w:rPr><w:b w:val="0"/></w:rPr><w:t>Not bold</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Note"/></w:pPr><w:r><w:t>Linked</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Aside"/></w:pPr><w:r><w:t>Own</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[("word/styles.xml", styles), ("word/document.xml", document)],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "# Heading\n\n**Bold by default**\n\nNot bold\n\n***Linked***\n\n*Own*\n",
+            markdown_doc.to_markdown(false)
+        );
+    }
+
+    #[test]
+    fn test_toggles_turned_off() {
+        let styles = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+            <w:style w:type="character" w:styleId="Strong"><w:name w:val="Strong"/><w:rPr><w:b/></w:rPr></w:style>
+            <w:style w:type="character" w:styleId="Hyperlink"><w:name w:val="Hyperlink"/><w:rPr><w:u w:val="single"/></w:rPr></w:style>
+            <w:style w:type="character" w:styleId="Light"><w:name w:val="Light"/><w:basedOn w:val="Strong"/><w:rPr><w:b w:val="0"/></w:rPr></w:style>
+            <w:style w:type="character" w:styleId="Secret"><w:name w:val="Secret"/><w:rPr><w:vanish/><w:caps/><w:smallCaps/></w:rPr></w:style>
+        </w:styles>"#;
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p>
+                <w:r><w:rPr><w:b w:val="0"/><w:i w:val="false"/><w:strike w:val="0"/></w:rPr><w:t xml:space="preserve">Plain, </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Strong"/><w:b w:val="0"/></w:rPr><w:t xml:space="preserve">not strong, </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Light"/></w:rPr><w:t xml:space="preserve">light, </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Hyperlink"/><w:u w:val="none"/></w:rPr><w:t xml:space="preserve">not underlined </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Secret"/><w:vanish w:val="0"/><w:caps w:val="false"/><w:smallCaps w:val="0"/></w:rPr><w:t xml:space="preserve">visible </w:t></w:r>
+                <w:r><w:rPr><w:em w:val="dot"/></w:rPr><w:t xml:space="preserve">dotted </w:t></w:r>
+                <w:r><w:rPr><w:rStyle w:val="Strong"/></w:rPr><w:t>and strong</w:t></w:r>
+            </w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[("word/styles.xml", styles), ("word/document.xml", document)],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "Plain, not strong, light, not underlined visible dotted **and strong**\n",
+            markdown_doc.to_markdown(false)
+        );
+    }
+
     #[test]
     fn test_images() {
         let markdown_pandoc = fs::read_to_string("./test/image.md").unwrap();
@@ -820,6 +2610,207 @@ mod tests {
         assert_eq!(markdown_pandoc, markdown);
     }
 
+    #[test]
+    fn test_inline_code() {
+        let markdown_pandoc = fs::read_to_string("./test/inline_code.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/inline_code.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!(markdown_pandoc, markdown);
+    }
+
+    #[test]
+    fn test_inline_formatting() {
+        let markdown_pandoc = fs::read_to_string("./test/inline_formatting.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/inline_formatting.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!(markdown_pandoc, markdown);
+
+        let underline = |underline| {
+            let options = MarkdownOptions {
+                underline,
+                ..MarkdownOptions::default()
+            };
+            let markdown = markdown_doc.to_markdown_with_options(&options);
+            markdown.lines().nth(4).unwrap().to_string()
+        };
+        assert_eq!(
+            "Some people use <u>single underlines for *emphasis*</u>.",
+            underline(UnderlineStyle::Html)
+        );
+        assert_eq!(
+            "Some people use single underlines for *emphasis*.",
+            underline(UnderlineStyle::Ignore)
+        );
+    }
+
+    #[test]
+    fn test_superscript_and_subscript() {
+        let markdown_pandoc = fs::read_to_string("./test/verbatim_subsuper.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/verbatim_subsuper.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!(markdown_pandoc, markdown);
+    }
+
+    #[test]
+    fn test_run_properties() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">H</w:t></w:r><w:r><w:rPr><w:vertAlign w:val="subscript"/></w:rPr><w:t>2</w:t></w:r><w:r><w:t xml:space="preserve">O, see </w:t></w:r>
+                <w:r><w:rPr><w:vertAlign w:val="superscript"/></w:rPr><w:t>note 1</w:t></w:r>
+                <w:r><w:t xml:space="preserve"> and </w:t></w:r><w:r><w:rPr><w:highlight w:val="yellow"/></w:rPr><w:t>this</w:t></w:r>
+                <w:r><w:t xml:space="preserve"> in </w:t></w:r><w:r><w:rPr><w:smallCaps/></w:rPr><w:t>Small Caps</w:t></w:r>
+                <w:r><w:t xml:space="preserve"> or </w:t></w:r><w:r><w:rPr><w:caps/></w:rPr><w:t>caps</w:t></w:r><w:r><w:rPr><w:caps w:val="0"/></w:rPr><w:t xml:space="preserve"> and </w:t></w:r>
+                <w:r><w:rPr><w:rFonts w:ascii="Arial" w:hAnsi="Arial"/><w:color w:val="FF0000"/></w:rPr><w:t>red</w:t></w:r>
+                <w:r><w:rPr><w:vanish/></w:rPr><w:t xml:space="preserve"> hidden</w:t></w:r><w:r><w:t>.</w:t></w:r>
+            </w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        let style = |text: &str| {
+            let paragraph = markdown_doc.paragraphs().next().unwrap();
+            let block = paragraph.blocks.iter().find(|block| block.text == text);
+            block.unwrap().style.clone().unwrap()
+        };
+        assert_eq!(Some("yellow".to_string()), style("this").highlight);
+        assert_eq!(Some("FF0000".to_string()), style("red").color);
+        assert!(style(" hidden").hidden);
+
+        assert_eq!(
+            "H~2~O, see ^note\\ 1^ and ==this== in [Small Caps]{.smallcaps} or CAPS and red.\n",
+            markdown_doc.to_markdown(false)
+        );
+        let options = MarkdownOptions {
+            formatting: FormattingStyle::Html,
+            fonts_and_colors: true,
+            hidden_text: true,
+            ..MarkdownOptions::default()
+        };
+        assert_eq!(
+            "H<sub>2</sub>O, see <sup>note 1</sup> and <mark>this</mark> in <span style=\"font-variant: small-caps\">Small Caps</span> or CAPS and <span style=\"font-family: 'Arial'; color: #FF0000\">red</span> hidden.\n",
+            markdown_doc.to_markdown_with_options(&options)
+        );
+        let options = MarkdownOptions {
+            formatting: FormattingStyle::None,
+            fonts_and_colors: true,
+            ..MarkdownOptions::default()
+        };
+        assert_eq!(
+            "H2O, see note 1 and this in Small Caps or caps and [red]{style=\"font-family: 'Arial'; color: #FF0000\"}.\n",
+            markdown_doc.to_markdown_with_options(&options)
+        );
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        let markdown_doc = MarkdownDocument::from_file("./test/codeblock.docx");
+        assert_eq!(
+            "This is some code:\n\n```\nreadDocx :: ReaderOptions\n         -> B.ByteString\n         -> Pandoc\n```\n\nfrom the beginning of the docx reader.\n",
+            markdown_doc.to_markdown(false)
+        );
+
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:r><w:t xml:space="preserve">Run </w:t></w:r><w:r><w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas"/></w:rPr><w:t>`cargo`</w:t></w:r></w:p>
+            <w:p><w:r><w:rPr><w:rFonts w:ascii="Courier New" w:hAnsi="Courier New"/></w:rPr><w:t>let fence = "```";</w:t></w:r></w:p>
+            <w:p><w:r><w:rPr><w:rFonts w:ascii="Courier New" w:hAnsi="Courier New"/></w:rPr><w:tab/><w:t>println!("{fence}");</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "Run `` `cargo` ``\n\n````\nlet fence = \"```\";\n\tprintln!(\"{fence}\");\n````\n",
+            markdown_doc.to_markdown(false)
+        );
+    }
+
+    #[test]
+    fn test_monospace_fonts() {
+        for font in [
+            "Consolas",
+            "Courier New",
+            "Roboto Mono",
+            "DejaVuSansMono",
+            "Source Code Pro",
+        ] {
+            assert!(styles::is_monospace_font(font), "{font}");
+        }
+        for font in [
+            "Monotype Corsiva",
+            "Code 128",
+            "IDAutomation Code39",
+            "Calibri",
+        ] {
+            assert!(!styles::is_monospace_font(font), "{font}");
+        }
+    }
+
+    #[test]
+    fn test_block_quotes() {
+        let markdown_doc = MarkdownDocument::from_file("./test/block_quotes.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert!(markdown.contains("\n\n> This is called the Intense Quote style.\n\n"));
+        assert!(markdown.contains("\n\n> I just indented this, so it looks like a block quote."));
+
+        let markdown_doc = MarkdownDocument::from_file("./test/custom_style_reference.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert!(markdown.contains(
+            "\n\n> One paragraph of text.\n>\n> And another paragraph of *really cool* text.\n"
+        ));
+
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:ind w:left="720"/></w:pPr><w:r><w:t>Outer</w:t></w:r></w:p>
+            <w:p><w:pPr><w:ind w:left="1440"/></w:pPr><w:r><w:t>Inner</w:t></w:r></w:p>
+            <w:p><w:pPr><w:ind w:left="720"/></w:pPr><w:r><w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas"/></w:rPr><w:t>let x = 1;</w:t></w:r></w:p>
+            <w:p><w:pPr><w:ind w:left="720"/></w:pPr><w:r><w:rPr><w:rFonts w:ascii="Consolas" w:hAnsi="Consolas"/></w:rPr><w:t>let y = 2;</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Body</w:t></w:r></w:p>
+            <w:p><w:pPr><w:ind w:left="720" w:hanging="360"/></w:pPr><w:r><w:t>Hanging</w:t></w:r></w:p>
+            <w:p><w:pPr><w:ind w:left="360"/></w:pPr><w:r><w:t>Indented</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "> Outer\n>\n> > Inner\n>\n> ```\n> let x = 1;\n> let y = 2;\n> ```\n\nBody\n\nHanging\n\nIndented\n",
+            markdown_doc.to_markdown(false)
+        );
+    }
+
+    #[test]
+    fn test_horizontal_rules() {
+        let styles = r#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+            <w:style w:type="paragraph" w:styleId="Separator"><w:name w:val="Separator"/><w:pPr><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr></w:pPr></w:style>
+            <w:style w:type="paragraph" w:styleId="SourceCode"><w:name w:val="Source Code"/></w:style>
+        </w:styles>"#;
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office"><w:body>
+            <w:p><w:r><w:t>Above a border</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pBdr><w:bottom w:val="single" w:sz="6" w:space="1" w:color="auto"/></w:pBdr></w:pPr></w:p>
+            <w:p><w:r><w:t>* * *</w:t></w:r></w:p>
+            <w:p><w:r><w:pict><v:rect id="_x0000_i1025" style="width:0;height:1.5pt" o:hralign="center" o:hrstd="t" o:hr="t" stroked="f"/></w:pict></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Separator"/></w:pPr></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Separator"/></w:pPr><w:r><w:t>Underlined with a border</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="Separator"/><w:pBdr><w:bottom w:val="nil"/></w:pBdr></w:pPr></w:p>
+            <w:p><w:r><w:t>**</w:t></w:r></w:p>
+            <w:p><w:r><w:t>===</w:t></w:r></w:p>
+            <w:p><w:pPr><w:pStyle w:val="SourceCode"/></w:pPr><w:r><w:t>----</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[("word/styles.xml", styles), ("word/document.xml", document)],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            concat!(
+                "Above a border\n\n---\n\n---\n\n---\n\n---\n\nUnderlined with a border\n\n",
+                "**\n\n===\n\n```\n----\n```\n"
+            ),
+            markdown_doc.to_markdown(false)
+        );
+        let rules = markdown_doc
+            .paragraphs()
+            .filter(|paragraph| paragraph.blocks.len() == 1)
+            .filter(|paragraph| paragraph.blocks[0].text_type == TextType::HorizontalRule)
+            .count();
+        assert_eq!(4, rules);
+    }
+
     #[test]
     fn test_links() {
         let markdown_pandoc = fs::read_to_string("./test/links.md").unwrap();
@@ -844,6 +2835,344 @@ mod tests {
         assert_eq!(markdown_pandoc, markdown);
     }
 
+    #[test]
+    fn test_merged_table_cells() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl><w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/></w:tblGrid>
+                <w:tr><w:trPr><w:tblHeader/></w:trPr>
+                    <w:tc><w:tcPr><w:gridSpan w:val="2"/></w:tcPr><w:p><w:r><w:t>Name</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:tcPr><w:vMerge w:val="restart"/></w:tcPr><w:p><w:r><w:t>Age</w:t></w:r></w:p></w:tc>
+                </w:tr>
+                <w:tr><w:trPr><w:tblHeader/></w:trPr>
+                    <w:tc><w:p><w:r><w:t>First</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>Last</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:tcPr><w:vMerge/></w:tcPr><w:p/></w:tc>
+                </w:tr>
+                <w:tr>
+                    <w:tc><w:p><w:r><w:t>Ada</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>Lovelace</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>36</w:t></w:r></w:p></w:tc>
+                </w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "<table>\n<colgroup>\n<col style=\"width: 33%\" />\n<col style=\"width: 33%\" />\n\
+             <col style=\"width: 33%\" />\n</colgroup>\n<thead>\n<tr>\n<th colspan=\"2\">Name</th>\n<th rowspan=\"2\">Age</th>\n</tr>\n\
+             <tr>\n<th>First</th>\n<th>Last</th>\n</tr>\n</thead>\n<tbody>\n\
+             <tr>\n<td>Ada</td>\n<td>Lovelace</td>\n<td>36</td>\n</tr>\n</tbody>\n</table>\n",
+            markdown_doc.to_markdown(false)
+        );
+        let options = MarkdownOptions {
+            merged_tables: MergedTableStyle::Grid,
+            ..Default::default()
+        };
+        assert_eq!(
+            "+------------------+-------+\n\
+             | Name             | Age   |\n\
+             +-------+----------+       |\n\
+             | First | Last     |       |\n\
+             +=======+==========+=======+\n\
+             | Ada   | Lovelace | 36    |\n\
+             +-------+----------+-------+\n",
+            markdown_doc.to_markdown_with_options(&options)
+        );
+        let MarkdownContent::Table(table) = &markdown_doc.content[0] else {
+            panic!("Expected a table");
+        };
+        let spans: Vec<Vec<(usize, usize)>> = table
+            .iter()
+            .map(|row| {
+                row.cells
+                    .iter()
+                    .map(|cell| (cell.colspan, cell.rowspan))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            vec![vec![(2, 1), (1, 2)], vec![(1, 1), (1, 1)], vec![(1, 1); 3]],
+            spans
+        );
+    }
+
+    #[test]
+    fn test_nested_tables() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl><w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="4000"/></w:tblGrid>
+                <w:tr>
+                    <w:tc><w:p><w:r><w:t>Outer</w:t></w:r></w:p></w:tc>
+                    <w:tc>
+                        <w:p><w:r><w:t>Before</w:t></w:r></w:p>
+                        <w:tbl><w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/></w:tblGrid>
+                            <w:tr><w:tc><w:p><w:r><w:t>a</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>b</w:t></w:r></w:p></w:tc></w:tr>
+                        </w:tbl>
+                        <w:p><w:r><w:t>After</w:t></w:r></w:p>
+                    </w:tc>
+                </w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "<table>\n<colgroup>\n<col style=\"width: 33%\" />\n<col style=\"width: 67%\" />\n\
+             </colgroup>\n<tbody>\n<tr>\n<td>Outer</td>\n<td><p>Before</p>\n<table>\n<colgroup>\n\
+             <col style=\"width: 50%\" />\n<col style=\"width: 50%\" />\n</colgroup>\n<tbody>\n<tr>\n<td>a</td>\n<td>b</td>\n</tr>\n</tbody>\n</table>\n\
+             <p>After</p></td>\n</tr>\n</tbody>\n</table>\n",
+            markdown_doc.to_markdown(false)
+        );
+        let options = MarkdownOptions {
+            merged_tables: MergedTableStyle::Grid,
+            ..Default::default()
+        };
+        assert_eq!(
+            "+--------+---------------+\n\
+             | Outer  | Before        |\n\
+             |        |               |\n\
+             |        | +-----+-----+ |\n\
+             |        | | a   | b   | |\n\
+             |        | +-----+-----+ |\n\
+             |        |               |\n\
+             |        | After         |\n\
+             +--------+---------------+\n",
+            markdown_doc.to_markdown_with_options(&options)
+        );
+        let MarkdownContent::Table(table) = &markdown_doc.content[0] else {
+            panic!("Expected a table");
+        };
+        let cell = &table[0].cells[1];
+        assert_eq!(3, cell.content.len());
+        assert!(matches!(cell.content[1], MarkdownContent::Table(_)));
+        assert_eq!(5, markdown_doc.paragraphs().count());
+    }
+
+    #[test]
+    fn test_table_formats() {
+        let markdown_doc = MarkdownDocument::from_file("./test/tables.docx");
+        let to_markdown = |table_format| {
+            let options = MarkdownOptions {
+                table_format,
+                ..Default::default()
+            };
+            markdown_doc.to_markdown_with_options(&options)
+        };
+        let simple = to_markdown(TableFormat::Simple);
+        assert!(simple.contains(
+            "Name            Game        Fame       Blame\n\
+             --------------  ----------  ---------  -----------------\n\
+             Lebron James    Basketball  Very High  Leaving Cleveland\n"
+        ));
+        assert!(
+            simple.contains("-------  ------\nSinple   Table\nWithout  Header\n-------  ------\n")
+        );
+        // Cells with several paragraphs do not fit a simple table
+        assert!(simple.contains("<td><p>Simple</p>\n<p>Multiparagraph</p></td>"));
+
+        let multiline = to_markdown(TableFormat::Multiline);
+        assert!(multiline.contains(
+            "Lebron James    Basketball     Very High      Leaving Cleveland\n\
+             \n\
+             Ryan Braun      Baseball       Moderate       Steroids\n"
+        ));
+        assert!(multiline.contains(&format!("{}\nName ", "-".repeat(56))));
+
+        let grid = to_markdown(TableFormat::Grid);
+        assert!(grid.contains(
+            "+---------+---------+\n\
+             | Sinple  | Table   |\n\
+             +---------+---------+\n\
+             | Without | Header  |\n\
+             +---------+---------+\n"
+        ));
+        assert!(
+            grid.contains("| Simple         | Table       |\n|                |             |\n")
+        );
+
+        let html = to_markdown(TableFormat::Html);
+        assert!(html.contains("<thead>\n<tr>\n<th>Name</th>\n"));
+
+        assert_eq!(
+            "## A table, with and without a header row\n",
+            to_markdown(TableFormat::Skip)
+        );
+    }
+
+    #[test]
+    fn test_table_alignment_and_widths() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl><w:tblGrid><w:gridCol w:w="1000"/><w:gridCol w:w="3000"/></w:tblGrid>
+                <w:tr><w:trPr><w:tblHeader/></w:trPr>
+                    <w:tc><w:p><w:r><w:t>名前</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>Amount</w:t></w:r></w:p></w:tc>
+                </w:tr>
+                <w:tr>
+                    <w:tc><w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:t>東京</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:pPr><w:jc w:val="right"/></w:pPr><w:r><w:t>1,000</w:t></w:r></w:p></w:tc>
+                </w:tr>
+                <w:tr>
+                    <w:tc><w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:t>Café</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:pPr><w:jc w:val="end"/></w:pPr><w:r><w:t>25</w:t></w:r></w:p></w:tc>
+                </w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "| 名前 | Amount |\n\
+             | :--: | -----: |\n\
+             | 東京 | 1,000  |\n\
+             | Café | 25     |\n",
+            markdown_doc.to_markdown(false)
+        );
+        let to_markdown = |table_format| {
+            let options = MarkdownOptions {
+                table_format,
+                ..Default::default()
+            };
+            markdown_doc.to_markdown_with_options(&options)
+        };
+        assert_eq!(
+            "+------+----------+\n\
+             | 名前 | Amount   |\n\
+             +:====:+=========:+\n\
+             | 東京 | 1,000    |\n\
+             +------+----------+\n\
+             | Café | 25       |\n\
+             +------+----------+\n",
+            to_markdown(TableFormat::Grid)
+        );
+        assert_eq!(
+            " 名前    Amount\n\
+             ------  -------\n \
+             東京     1,000\n \
+             Café        25\n",
+            to_markdown(TableFormat::Simple)
+        );
+        let html = to_markdown(TableFormat::Html);
+        assert!(html.contains(
+            "<colgroup>\n<col style=\"width: 25%\" />\n<col style=\"width: 75%\" />\n</colgroup>\n"
+        ));
+        assert!(html.contains("<td style=\"text-align: right;\">25</td>"));
+
+        let json = markdown_doc.to_json(false);
+        assert!(json.contains(r#""width":1000,"alignment":"Center"}"#));
+    }
+
+    #[test]
+    fn test_export_tables() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl><w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/></w:tblGrid>
+                <w:tr><w:trPr><w:tblHeader/></w:trPr>
+                    <w:tc><w:tcPr><w:gridSpan w:val="2"/></w:tcPr><w:p><w:r><w:t>Name</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:tcPr><w:vMerge w:val="restart"/></w:tcPr><w:p><w:r><w:t>Age</w:t></w:r></w:p></w:tc>
+                </w:tr>
+                <w:tr><w:trPr><w:tblHeader/></w:trPr>
+                    <w:tc><w:p><w:r><w:t>First</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>Last</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:tcPr><w:vMerge/></w:tcPr><w:p/></w:tc>
+                </w:tr>
+                <w:tr>
+                    <w:tc><w:p><w:r><w:t>Ada</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>Lovelace</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>36</w:t></w:r></w:p></w:tc>
+                </w:tr>
+            </w:tbl>
+            <w:p><w:r><w:t>Prices</w:t></w:r></w:p>
+            <w:tbl><w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/></w:tblGrid>
+                <w:tr>
+                    <w:tc><w:p><w:r><w:t>Item</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>Price</w:t></w:r></w:p></w:tc>
+                </w:tr>
+                <w:tr>
+                    <w:tc><w:p><w:r><w:t>Bolts, "M8"</w:t></w:r></w:p><w:p><w:r><w:t>Steel</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>1,50</w:t></w:r></w:p></w:tc>
+                </w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(2, markdown_doc.tables().len());
+
+        let csv = markdown_doc
+            .tables_to_strings(&TableExportOptions::default())
+            .unwrap();
+        assert_eq!(
+            vec![
+                "Name,Name,Age\r\nFirst,Last,Age\r\nAda,Lovelace,36\r\n",
+                "Item,Price\r\n\"Bolts, \"\"M8\"\"\nSteel\",\"1,50\"\r\n",
+            ],
+            csv
+        );
+        let options = TableExportOptions {
+            format: TableExportFormat::Tsv,
+            ..Default::default()
+        };
+        assert_eq!(
+            "Item\tPrice\nBolts, \"M8\" Steel\t1,50\n",
+            markdown_doc.tables_to_strings(&options).unwrap()[1]
+        );
+
+        let options = TableExportOptions {
+            format: TableExportFormat::Json,
+            ..Default::default()
+        };
+        let json = markdown_doc.tables_to_strings(&options).unwrap();
+        let records: serde_json::Value = serde_json::from_str(&json[0]).unwrap();
+        assert_eq!(
+            serde_json::json!([{"Name First": "Ada", "Name Last": "Lovelace", "Age": "36"}]),
+            records
+        );
+        let records: serde_json::Value = serde_json::from_str(&json[1]).unwrap();
+        assert_eq!(
+            serde_json::json!([
+                {"column_1": "Item", "column_2": "Price"},
+                {"column_1": "Bolts, \"M8\"\nSteel", "column_2": "1,50"}
+            ]),
+            records
+        );
+        let options = TableExportOptions {
+            format: TableExportFormat::Json,
+            first_row_as_header: true,
+        };
+        let json = markdown_doc.tables_to_strings(&options).unwrap();
+        let records: serde_json::Value = serde_json::from_str(&json[1]).unwrap();
+        assert_eq!(
+            serde_json::json!([{"Item": "Bolts, \"M8\"\nSteel", "Price": "1,50"}]),
+            records
+        );
+    }
+
+    #[test]
+    fn test_export_table_keys_taken() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:tbl>
+                <w:tr>
+                    <w:trPr><w:tblHeader/></w:trPr>
+                    <w:tc><w:p><w:r><w:t>name</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>name</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>name_2</w:t></w:r></w:p></w:tc>
+                </w:tr>
+                <w:tr>
+                    <w:tc><w:p><w:r><w:t>Jane</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>John</w:t></w:r></w:p></w:tc>
+                    <w:tc><w:p><w:r><w:t>Joe</w:t></w:r></w:p></w:tc>
+                </w:tr>
+            </w:tbl>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        let options = TableExportOptions {
+            format: TableExportFormat::Json,
+            ..Default::default()
+        };
+        let json = markdown_doc.tables_to_strings(&options).unwrap();
+        let records: serde_json::Value = serde_json::from_str(&json[0]).unwrap();
+        assert_eq!(
+            serde_json::json!([{"name": "Jane", "name_3": "John", "name_2": "Joe"}]),
+            records
+        );
+    }
+
     #[test]
     fn test_table_with_list_cell() {
         let markdown_pandoc = fs::read_to_string("./test/table_with_list_cell.md").unwrap();
@@ -852,6 +3181,552 @@ mod tests {
         assert_eq!(markdown_pandoc, markdown);
     }
 
+    #[test]
+    fn test_from_bytes() {
+        let markdown_pandoc = fs::read_to_string("./test/headers.md").unwrap();
+        let bytes = fs::read("./test/headers.docx").unwrap();
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(markdown_pandoc, markdown_doc.to_markdown(false));
+    }
+
+    #[test]
+    fn test_try_from_file_errors() {
+        let missing = MarkdownDocument::try_from_file("./test/does-not-exist.docx");
+        assert!(matches!(missing, Err(DocxParserError::Io(_))));
+
+        let not_a_zip = MarkdownDocument::try_from_file("./test/headers.md");
+        assert!(matches!(not_a_zip, Err(DocxParserError::Zip(_))));
+    }
+
+    #[test]
+    fn test_missing_and_unsupported_parts() {
+        use std::io::{Cursor, Write};
+        use zip::{write::SimpleFileOptions, ZipWriter};
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file("[Content_Types].xml", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"<Types/>").unwrap();
+        let archive = zip.finish().unwrap();
+        let result = MarkdownDocument::from_reader(archive);
+        assert!(matches!(result, Err(DocxParserError::MissingPart(part)) if part == "_rels/.rels"));
+
+        let mut compound_file = vec![0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+        compound_file.extend_from_slice(&[0; 504]);
+        let result = MarkdownDocument::from_bytes(&compound_file);
+        assert!(matches!(result, Err(DocxParserError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_utf16_parts() {
+        use std::io::{Cursor, Write};
+        use zip::{write::SimpleFileOptions, ZipWriter};
+
+        let markdown_pandoc = fs::read_to_string("./test/headers.md").unwrap();
+        let mut archive = ZipArchive::new(File::open("./test/headers.docx").unwrap()).unwrap();
+        let mut document = String::new();
+        archive
+            .by_name("word/document.xml")
+            .unwrap()
+            .read_to_string(&mut document)
+            .unwrap();
+        let utf16 = |xml: &str, to_bytes: fn(u16) -> [u8; 2]| -> Vec<u8> {
+            [0xFEFF]
+                .into_iter()
+                .chain(xml.encode_utf16())
+                .flat_map(to_bytes)
+                .collect()
+        };
+        let mut with_parts = |parts: &[(&str, Vec<u8>)]| {
+            let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+            for i in 0..archive.len() {
+                let file = archive.by_index_raw(i).unwrap();
+                if !parts.iter().any(|(name, _)| *name == file.name()) {
+                    zip.raw_copy_file(file).unwrap();
+                }
+            }
+            for (name, content) in parts {
+                zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+                zip.write_all(content).unwrap();
+            }
+            zip.finish().unwrap().into_inner()
+        };
+
+        // The custom XML of SharePoint is often UTF-16 too, but is not read at all
+        let xml = "<?xml version=\"1.0\" encoding=\"utf-16\"?><root/>";
+        let bytes = with_parts(&[
+            ("word/document.xml", utf16(&document, u16::to_be_bytes)),
+            ("customXml/item1.xml", utf16(xml, u16::to_le_bytes)),
+        ]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(markdown_pandoc, markdown_doc.to_markdown(false));
+
+        let latin1: Vec<u8> = document
+            .replace("Header", "Kopf\u{fc}")
+            .chars()
+            .map(|c| c as u8)
+            .collect();
+        let bytes = with_parts(&[("word/document.xml", latin1)]);
+        let result = MarkdownDocument::from_bytes(&bytes);
+        assert!(matches!(result, Err(DocxParserError::Unsupported(_))));
+    }
+
+    #[test]
+    fn test_track_changes_insertion() {
+        let markdown_pandoc = fs::read_to_string("./test/track_changes_insertion.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/track_changes_insertion.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!(markdown_pandoc, markdown);
+    }
+
+    #[test]
+    fn test_track_changes_deletion() {
+        let markdown_pandoc = fs::read_to_string("./test/track_changes_deletion.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/track_changes_deletion.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!(markdown_pandoc, markdown);
+    }
+
+    #[test]
+    fn test_track_changes_move() {
+        let markdown_pandoc = fs::read_to_string("./test/track_changes_move.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/track_changes_move.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!(markdown_pandoc, markdown);
+    }
+
+    #[test]
+    fn test_track_changes_reject_and_all() {
+        let markdown_doc = MarkdownDocument::from_file("./test/track_changes_deletion.docx");
+        let reject = MarkdownOptions {
+            revisions: RevisionMode::Reject,
+            ..Default::default()
+        };
+        assert_eq!(
+            "This is a text with an excessively modified deletion.\n",
+            markdown_doc.to_markdown_with_options(&reject)
+        );
+        let all = MarkdownOptions {
+            revisions: RevisionMode::All,
+            ..Default::default()
+        };
+        assert_eq!(
+            "This is a text with a{--n excessively modified--} deletion.\n",
+            markdown_doc.to_markdown_with_options(&all)
+        );
+
+        let mut markdown_doc = MarkdownDocument::from_file("./test/track_changes_move.docx");
+        markdown_doc.apply_revisions(RevisionMode::Reject);
+        assert_eq!(3, markdown_doc.content.len());
+        let json = markdown_doc.to_json(false);
+        assert!(!json.contains("revision"));
+    }
+
+    #[test]
+    fn test_track_changes_around_links_and_fields() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">See </w:t></w:r>
+                <w:ins w:author="Ada"><w:hyperlink w:anchor="intro"><w:r><w:t>the intro</w:t></w:r></w:hyperlink><w:smartTag w:element="place"><w:r><w:t xml:space="preserve"> in Amsterdam</w:t></w:r></w:smartTag></w:ins>
+                <w:del w:author="Ada"><w:fldSimple w:instr="PAGE"><w:r><w:delText>page 1</w:delText></w:r></w:fldSimple></w:del>
+                <w:r><w:t>.</w:t></w:r>
+            </w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        let markdown = |revisions| {
+            markdown_doc.to_markdown_with_options(&MarkdownOptions {
+                revisions,
+                ..Default::default()
+            })
+        };
+        assert_eq!(
+            "See [the intro](#intro) in Amsterdam.\n",
+            markdown(RevisionMode::Accept)
+        );
+        assert_eq!("See page 1.\n", markdown(RevisionMode::Reject));
+        assert_eq!(
+            "See {++[the intro](#intro) in Amsterdam++}{--page 1--}.\n",
+            markdown(RevisionMode::All)
+        );
+    }
+
+    #[test]
+    fn test_track_changes_paragraph_marks() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:pPr><w:rPr><w:del w:author="Ada"/></w:rPr></w:pPr><w:r><w:t xml:space="preserve">Joined </w:t></w:r></w:p>
+            <w:p><w:r><w:t>paragraph.</w:t></w:r></w:p>
+            <w:p><w:pPr><w:rPr><w:ins w:author="Ada"/></w:rPr></w:pPr><w:r><w:t xml:space="preserve">Split </w:t></w:r></w:p>
+            <w:p><w:r><w:t>paragraph.</w:t></w:r></w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let mut markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        let markdown = |markdown_doc: &MarkdownDocument, revisions| {
+            markdown_doc.to_markdown_with_options(&MarkdownOptions {
+                revisions,
+                ..Default::default()
+            })
+        };
+        assert_eq!(
+            "Joined paragraph.\n\nSplit \n\nparagraph.\n",
+            markdown(&markdown_doc, RevisionMode::Accept)
+        );
+        assert_eq!(
+            "Joined \n\nparagraph.\n\nSplit paragraph.\n",
+            markdown(&markdown_doc, RevisionMode::Reject)
+        );
+        assert_eq!(
+            "Joined \n\nparagraph.\n\nSplit \n\nparagraph.\n",
+            markdown(&markdown_doc, RevisionMode::All)
+        );
+
+        markdown_doc.apply_revisions(RevisionMode::Accept);
+        assert_eq!(3, markdown_doc.content.len());
+        assert!(!markdown_doc.to_json(false).contains("markRevision"));
+    }
+
+    /// Copy a fixture, replacing or adding the given parts.
+    fn fixture_with_parts(fixture: &str, parts: &[(&str, &str)]) -> Vec<u8> {
+        use std::io::{Cursor, Write};
+        use zip::{write::SimpleFileOptions, ZipWriter};
+
+        let mut archive = ZipArchive::new(File::open(fixture).unwrap()).unwrap();
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let file = archive.by_index_raw(i).unwrap();
+            if !parts.iter().any(|(name, _)| *name == file.name()) {
+                zip.raw_copy_file(file).unwrap();
+            }
+        }
+        for (name, content) in parts {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_comments() {
+        let markdown_pandoc = fs::read_to_string("./test/comments.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/comments.docx");
+        assert_eq!(markdown_pandoc, markdown_doc.to_markdown(false));
+
+        assert_eq!(5, markdown_doc.comments.len());
+        let comment = &markdown_doc.comments[1];
+        assert_eq!(Some("Jesse Rosenthal"), comment.author.as_deref());
+        assert_eq!(Some("2016-05-09T16:13:00Z"), comment.date.as_deref());
+        assert_eq!("a new paragraph.\nAnd so", comment.anchor);
+        // The range of comment 4 is never closed, so it ends with the paragraph
+        assert_eq!("comment in a comment.", markdown_doc.comments[4].anchor);
+
+        let critic_markup = MarkdownOptions {
+            comments: CommentStyle::CriticMarkup,
+            ..Default::default()
+        };
+        let markdown = markdown_doc.to_markdown_with_options(&critic_markup);
+        assert!(markdown.starts_with("I want {==some text to have a comment==}{>>Jesse Rosenthal (2016-05-09): I left a comment.<<} on it."));
+
+        let html = MarkdownOptions {
+            comments: CommentStyle::Html,
+            ..Default::default()
+        };
+        let markdown = markdown_doc.to_markdown_with_options(&html);
+        assert!(markdown.starts_with(r#"I want <span class="comment-start" id="0" author="Jesse Rosenthal" date="2016-05-09T16:13:00Z">I left a comment.</span>some text to have a comment <span class="comment-end" id="0"></span>on it."#));
+
+        let footnotes = MarkdownOptions {
+            comments: CommentStyle::Footnotes,
+            ..Default::default()
+        };
+        let markdown = markdown_doc.to_markdown_with_options(&footnotes);
+        assert!(markdown.starts_with("I want some text to have a comment[^comment-0] on it."));
+        assert!(
+            markdown.contains("\n[^comment-0]: Jesse Rosenthal (2016-05-09): I left a comment.\n")
+        );
+    }
+
+    #[test]
+    fn test_comment_replies() {
+        let comments = r#"<w:comments xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:w14="http://schemas.microsoft.com/office/word/2010/wordml">
+            <w:comment w:id="0" w:author="Jesse Rosenthal" w:date="2016-05-09T16:13:00Z"><w:p w14:paraId="0A000001"><w:r><w:t>I left a comment.</w:t></w:r></w:p></w:comment>
+            <w:comment w:id="5" w:author="Jane Doe" w:date="2016-05-10T09:00:00Z"><w:p w14:paraId="0A000002"><w:r><w:t>I agree.</w:t></w:r></w:p></w:comment>
+        </w:comments>"#;
+        let comments_extended = r#"<w15:commentsEx xmlns:w15="http://schemas.microsoft.com/office/word/2012/wordml">
+            <w15:commentEx w15:paraId="0A000001" w15:done="0"/>
+            <w15:commentEx w15:paraId="0A000002" w15:paraIdParent="0A000001" w15:done="0"/>
+        </w15:commentsEx>"#;
+        let bytes = fixture_with_parts(
+            "./test/comments.docx",
+            &[
+                ("word/comments.xml", comments),
+                ("word/commentsExtended.xml", comments_extended),
+            ],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(1, markdown_doc.comments.len());
+        let replies = &markdown_doc.comments[0].replies;
+        assert_eq!(1, replies.len());
+        assert_eq!(Some("Jane Doe"), replies[0].author.as_deref());
+        assert_eq!("some text to have a comment", replies[0].anchor);
+
+        let options = MarkdownOptions {
+            comments: CommentStyle::Footnotes,
+            ..Default::default()
+        };
+        let markdown = markdown_doc.to_markdown_with_options(&options);
+        assert!(markdown.ends_with("[^comment-0]: Jesse Rosenthal (2016-05-09): I left a comment.\n\n    Jane Doe (2016-05-10): I agree.\n"));
+    }
+
+    #[test]
+    fn test_notes() {
+        let markdown_pandoc = fs::read_to_string("./test/notes.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/notes.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!(markdown_pandoc, markdown);
+    }
+
+    #[test]
+    fn test_link_in_notes() {
+        let markdown_pandoc = fs::read_to_string("./test/link_in_notes.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/link_in_notes.docx");
+        let markdown = markdown_doc.to_markdown(false);
+        assert_eq!(markdown_pandoc, markdown);
+
+        let note = &markdown_doc.notes[0];
+        assert_eq!(NoteKind::Footnote, note.kind);
+        assert_eq!(Some(1), note.number);
+        assert_eq!(TextType::Link, note.content[0].blocks[1].text_type);
+    }
+
+    #[test]
+    fn test_endnotes() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:p><w:r><w:t>First</w:t></w:r><w:r><w:endnoteReference w:id="1"/></w:r><w:r><w:t> and second.</w:t></w:r><w:r><w:footnoteReference w:id="20"/></w:r></w:p>
+        </w:body></w:document>"#;
+        let endnotes = r#"<w:endnotes xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:endnote w:type="separator" w:id="-1"><w:p><w:r><w:separator/></w:r></w:p></w:endnote>
+            <w:endnote w:id="1"><w:p><w:r><w:endnoteRef/></w:r><w:r><w:t xml:space="preserve"> An endnote.</w:t></w:r></w:p><w:p><w:r><w:t>With a second paragraph.</w:t></w:r></w:p></w:endnote>
+        </w:endnotes>"#;
+        let bytes = fixture_with_parts(
+            "./test/notes.docx",
+            &[
+                ("word/document.xml", document),
+                ("word/endnotes.xml", endnotes),
+            ],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "First[^1] and second.[^2]\n\n[^1]: An endnote.\n\n    With a second paragraph.\n\n[^2]: My note.\n",
+            markdown_doc.to_markdown(false)
+        );
+    }
+
+    #[test]
+    fn test_headers_and_footers() {
+        let w = r#"xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships""#;
+        let document = format!(
+            r#"<w:document {w}><w:body>
+            <w:p><w:pPr><w:sectPr><w:headerReference w:type="default" r:id="rId10"/><w:headerReference w:type="first" r:id="rId11"/><w:footerReference w:type="default" r:id="rId12"/></w:sectPr></w:pPr><w:r><w:t>First section.</w:t></w:r></w:p>
+            <w:p><w:r><w:t>Second section.</w:t></w:r></w:p>
+            <w:sectPr><w:footerReference w:type="default" r:id="rId13"/></w:sectPr>
+        </w:body></w:document>"#
+        );
+        let rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Id="rId2" Target="styles.xml"/>
+            <Relationship Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/header" Id="rId10" Target="header1.xml"/>
+            <Relationship Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/header" Id="rId11" Target="header2.xml"/>
+            <Relationship Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/footer" Id="rId12" Target="footer1.xml"/>
+            <Relationship Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/footer" Id="rId13" Target="footer2.xml"/>
+        </Relationships>"#;
+        let part = |tag: &str, text: &str| {
+            format!(r#"<w:{tag} {w}><w:p><w:r><w:t>{text}</w:t></w:r></w:p></w:{tag}>"#)
+        };
+        let (header1, header2) = (part("hdr", "CONFIDENTIAL"), part("hdr", "Cover page"));
+        let (footer1, footer2) = (part("ftr", "Draft"), part("ftr", "Page \"2\""));
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[
+                ("word/document.xml", &document),
+                ("word/_rels/document.xml.rels", rels),
+                ("word/header1.xml", &header1),
+                ("word/header2.xml", &header2),
+                ("word/footer1.xml", &footer1),
+                ("word/footer2.xml", &footer2),
+            ],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(2, markdown_doc.sections.len());
+        let second = &markdown_doc.sections[1];
+        // The headers are inherited from the first section, the footer is replaced
+        assert_eq!(2, second.headers.len());
+        assert!(second.header(HeaderFooterKind::First).is_some());
+        assert_eq!(1, second.footers.len());
+
+        assert_eq!(
+            "First section.\n\nSecond section.\n",
+            markdown_doc.to_markdown(false)
+        );
+        let blocks = MarkdownOptions {
+            headers_footers: HeaderFooterStyle::Blocks,
+            ..Default::default()
+        };
+        assert_eq!(
+            "CONFIDENTIAL\n\nFirst section.\n\nSecond section.\n\nPage \"2\"\n",
+            markdown_doc.to_markdown_with_options(&blocks)
+        );
+        let front_matter = MarkdownOptions {
+            headers_footers: HeaderFooterStyle::FrontMatter,
+            ..Default::default()
+        };
+        assert_eq!(
+            "---\nheader: \"CONFIDENTIAL\"\nfooter: \"Page \\\"2\\\"\"\n---\n\nFirst section.\n\nSecond section.\n",
+            markdown_doc.to_markdown_with_options(&front_matter)
+        );
+    }
+
+    #[test]
+    fn test_first_page_header_and_no_front_matter() {
+        let front_matter = MarkdownOptions {
+            headers_footers: HeaderFooterStyle::FrontMatter,
+            ..Default::default()
+        };
+        let markdown_pandoc = fs::read_to_string("./test/headers.md").unwrap();
+        let markdown_doc = MarkdownDocument::from_file("./test/headers.docx");
+        assert_eq!(
+            markdown_pandoc,
+            markdown_doc.to_markdown_with_options(&front_matter)
+        );
+
+        let w = r#"xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships""#;
+        let document = format!(
+            r#"<w:document {w}><w:body>
+            <w:p><w:r><w:t>Text.</w:t></w:r></w:p>
+            <w:sectPr><w:headerReference w:type="first" r:id="rId10"/><w:titlePg/></w:sectPr>
+        </w:body></w:document>"#
+        );
+        let rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+            <Relationship Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/header" Id="rId10" Target="/word/header1.xml"/>
+        </Relationships>"#;
+        let header = format!(r#"<w:hdr {w}><w:p><w:r><w:t>SECRET</w:t></w:r></w:p></w:hdr>"#);
+        let bytes = fixture_with_parts(
+            "./test/headers.docx",
+            &[
+                ("word/document.xml", &document),
+                ("word/_rels/document.xml.rels", rels),
+                ("word/header1.xml", &header),
+            ],
+        );
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "---\nheader: \"SECRET\"\n---\n\nText.\n",
+            markdown_doc.to_markdown_with_options(&front_matter)
+        );
+    }
+
+    #[test]
+    fn test_content_controls() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+            <w:sdt><w:sdtPr><w:id w:val="1"/><w:docPartObj><w:docPartGallery w:val="Table of Contents"/></w:docPartObj></w:sdtPr>
+                <w:sdtContent><w:p><w:r><w:t>Contents</w:t></w:r></w:p></w:sdtContent></w:sdt>
+            <w:p><w:r><w:t>Name: </w:t></w:r><w:sdt><w:sdtPr><w:alias w:val="Customer name"/><w:tag w:val="customer"/><w:text/></w:sdtPr>
+                <w:sdtContent><w:r><w:t>ACME</w:t></w:r></w:sdtContent></w:sdt><w:r><w:t>.</w:t></w:r></w:p>
+            <w:tbl><w:tblGrid><w:gridCol w:w="2000"/></w:tblGrid><w:sdt><w:sdtContent><w:tr><w:trPr><w:tblHeader/></w:trPr><w:tc><w:sdt><w:sdtContent><w:p><w:r><w:t>In a cell</w:t></w:r></w:p></w:sdtContent></w:sdt></w:tc></w:tr></w:sdtContent></w:sdt></w:tbl>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            "Contents\n\nName: ACME.\n\n| In a cell |\n| --------- |\n",
+            markdown_doc.to_markdown(false)
+        );
+
+        let MarkdownContent::Sdt(toc) = &markdown_doc.content[0] else {
+            panic!("Expected a content control");
+        };
+        assert_eq!(SdtType::DocPartObj, toc.properties.sdt_type);
+        assert_eq!(Some("Table of Contents"), toc.properties.gallery.as_deref());
+
+        let json = markdown_doc.to_json(false);
+        assert!(json.contains(
+            r#""text":"ACME","sdt":{"tag":"customer","alias":"Customer name","type":"Text"}"#
+        ));
+    }
+
+    #[test]
+    fn test_form_fields() {
+        let document = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:w14="http://schemas.microsoft.com/office/word/2010/wordml"><w:body>
+            <w:p><w:r><w:t>Name: </w:t></w:r><w:sdt><w:sdtPr><w:alias w:val="Name"/><w:tag w:val="name"/><w:text/></w:sdtPr>
+                <w:sdtContent><w:r><w:t>Jane </w:t></w:r><w:r><w:t>Doe</w:t></w:r></w:sdtContent></w:sdt></w:p>
+            <w:p><w:sdt><w:sdtPr><w:alias w:val="Country"/><w:dropDownList><w:listItem w:displayText="The Netherlands" w:value="NL"/></w:dropDownList></w:sdtPr>
+                <w:sdtContent><w:r><w:t>The Netherlands</w:t></w:r></w:sdtContent></w:sdt>
+                <w:sdt><w:sdtPr><w:tag w:val="remarks"/><w:showingPlcHdr/><w:text/></w:sdtPr>
+                <w:sdtContent><w:r><w:t>Click here to enter text.</w:t></w:r></w:sdtContent></w:sdt></w:p>
+            <w:sdt><w:sdtPr><w:group/></w:sdtPr><w:sdtContent>
+                <w:sdt><w:sdtPr><w:tag w:val="date"/><w:date w:fullDate="2024-01-31T00:00:00Z"><w:dateFormat w:val="d-M-yyyy"/></w:date></w:sdtPr>
+                    <w:sdtContent><w:p><w:r><w:t>31-1-2024</w:t></w:r></w:p></w:sdtContent></w:sdt>
+            </w:sdtContent></w:sdt>
+            <w:tbl><w:tblGrid><w:gridCol w:w="2000"/><w:gridCol w:w="2000"/></w:tblGrid><w:tr>
+                <w:tc><w:sdt><w:sdtPr><w:tag w:val="address"/></w:sdtPr><w:sdtContent>
+                    <w:p><w:r><w:t>Main street 1</w:t></w:r></w:p><w:p><w:r><w:t>Amsterdam</w:t></w:r></w:p>
+                </w:sdtContent></w:sdt></w:tc>
+                <w:tc><w:p><w:sdt><w:sdtPr><w:tag w:val="agree"/><w14:checkbox><w14:checked w14:val="1"/></w14:checkbox></w:sdtPr>
+                    <w:sdtContent><w:r><w:t>☒</w:t></w:r></w:sdtContent></w:sdt></w:p></w:tc>
+            </w:tr></w:tbl>
+            <w:p><w:sdt><w:sdtPr><w:tag w:val="name"/><w:text/></w:sdtPr><w:sdtContent><w:r><w:t>John</w:t></w:r></w:sdtContent></w:sdt></w:p>
+        </w:body></w:document>"#;
+        let bytes = fixture_with_parts("./test/headers.docx", &[("word/document.xml", document)]);
+        let markdown_doc = MarkdownDocument::from_bytes(&bytes).unwrap();
+
+        let fields = markdown_doc.form_fields();
+        assert_eq!(7, fields.len());
+        assert_eq!(Some("Name"), fields[0].alias.as_deref());
+        assert_eq!(Some(FieldValue::Text("Jane Doe".into())), fields[0].value);
+        assert_eq!("Country", fields[1].name());
+        assert_eq!(SdtType::DropDownList, fields[1].field_type);
+        assert_eq!(None, fields[2].value);
+        assert_eq!(Some(FieldValue::Text("2024-01-31".into())), fields[3].value);
+        assert_eq!(SdtType::RichText, fields[4].field_type);
+        assert_eq!(
+            Some(FieldValue::Text("Main street 1\nAmsterdam".into())),
+            fields[4].value
+        );
+        assert_eq!(Some(FieldValue::Checked(true)), fields[5].value);
+
+        assert_eq!(
+            concat!(
+                r#"{"name":{"type":"Text","value":"Jane Doe"},"#,
+                r#""Country":{"type":"DropDownList","value":"The Netherlands"},"#,
+                r#""remarks":{"type":"Text","value":null},"#,
+                r#""date":{"type":"Date","value":"2024-01-31"},"#,
+                r#""address":{"type":"RichText","value":"Main street 1\nAmsterdam"},"#,
+                r#""agree":{"type":"CheckBox","value":true},"#,
+                r#""name_2":{"type":"Text","value":"John"}}"#
+            ),
+            markdown_doc.form_fields_to_json(false).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_form_field_names_taken() {
+        let field = |tag: &str, value: &str| sdt::FormField {
+            tag: Some(tag.into()),
+            alias: None,
+            field_type: SdtType::Text,
+            value: Some(FieldValue::Text(value.into())),
+        };
+        let fields = [
+            field("name", "Jane"),
+            field("name", "John"),
+            field("name_2", "Joe"),
+        ];
+        assert_eq!(
+            concat!(
+                r#"{"name":{"type":"Text","value":"Jane"},"#,
+                r#""name_3":{"type":"Text","value":"John"},"#,
+                r#""name_2":{"type":"Text","value":"Joe"}}"#
+            ),
+            serde_json::to_string(&sdt::FormFieldMap(&fields)).unwrap()
+        );
+    }
+
     #[test]
     fn test_tables_separated_with_rawblock() {
         let markdown_pandoc =
@@ -1,17 +1,26 @@
-use clap::{command, Parser};
-use docx_parser::MarkdownDocument;
+use clap::{Parser, Subcommand};
+use docx_parser::{
+    CommentStyle, FormattingStyle, HeaderFooterStyle, MarkdownDocument, MarkdownOptions,
+    MergedTableStyle, RevisionMode, StyleMap, TableExportFormat, TableExportOptions, TableFormat,
+    UnderlineStyle,
+};
 use std::fs;
+use std::io::{self, Read};
 
 #[derive(Parser)]
 #[command(name = "docx-parser")]
 #[command(version = "0.1.1")]
 #[command(author = "Erik Vullings <erik.vullings@gmail.com>")]
 #[command(about = "Processes a DOCX file and outputs as Markdown or JSON", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    /// The input DOCX file
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// The input DOCX file, or - to read it from stdin
     // #[arg(short, long, value_name = "FILE", required = true)]
-    #[arg(value_name = "FILE", index = 1)]
-    input: String,
+    #[arg(value_name = "FILE", index = 1, required = true)]
+    input: Option<String>,
 
     /// Sets the output destination. Default is console.
     #[arg(short, long)]
@@ -20,12 +29,109 @@ struct Cli {
     /// Sets the output format. Default is markdown. Options: md, json, and pretty_json.
     #[arg(short, long)]
     format: Option<String>,
+
+    /// Sets how tracked changes are handled. Options: accept, reject, and all (as CriticMarkup).
+    #[arg(short, long, default_value = "accept")]
+    revisions: RevisionMode,
+
+    /// Sets how comments are rendered in Markdown. Options: none, criticmarkup, html, and footnotes.
+    #[arg(short, long, default_value = "none")]
+    comments: CommentStyle,
+
+    /// Sets how page headers and footers are rendered in Markdown. Options: none, blocks, and front-matter.
+    #[arg(long, default_value = "none")]
+    headers_footers: HeaderFooterStyle,
+
+    /// Sets the heading level of a paragraph style by its name or id, e.g. "Chapter=1", or 0 for no heading. Can be repeated.
+    #[arg(long, value_name = "STYLE=LEVEL", value_parser = parse_heading_style)]
+    heading_style: Vec<(String, usize)>,
+
+    /// Reads a style map from a file, which maps paragraph and character styles to Markdown, e.g. "p[style-name='Note Box'] => blockquote".
+    #[arg(long, value_name = "FILE")]
+    style_map: Option<String>,
+
+    /// Keeps custom styles as pandoc divs and spans, e.g. ::: {custom-style="Note Box"}, so pandoc can restore them.
+    #[arg(long)]
+    custom_styles: bool,
+
+    /// Sets how superscript, subscript, highlight, small caps and all caps are rendered in Markdown. Options: none, markdown (e.g. ^x^ and ==x==), and html (e.g. <sup>x</sup>).
+    #[arg(long, default_value = "markdown")]
+    formatting: FormattingStyle,
+
+    /// Keeps the font and color of text, as CSS in pandoc spans or HTML.
+    #[arg(long)]
+    fonts_and_colors: bool,
+
+    /// Keeps hidden text.
+    #[arg(long)]
+    hidden_text: bool,
+
+    /// Sets how underlined text is rendered in Markdown. Options: ignore, html (<u>x</u>), and pandoc ([x]{.underline}).
+    #[arg(long, default_value = "pandoc")]
+    underline: UnderlineStyle,
+
+    /// Sets how tables are rendered in Markdown. Options: pipe, grid, simple, multiline (pandoc tables), html, and skip.
+    #[arg(long, default_value = "pipe")]
+    table_format: TableFormat,
+
+    /// Sets how tables that the table format cannot represent, like tables with merged cells or, for pipe tables, without a header row, are rendered in Markdown. Options: html and grid (pandoc grid tables).
+    #[arg(long, default_value = "html")]
+    merged_tables: MergedTableStyle,
+
+    /// Writes the data in each table to a file in the output directory instead, e.g. table-1.csv. Options: csv, tsv, and json (records keyed by the header row).
+    #[arg(long, value_name = "FORMAT")]
+    tables: Option<TableExportFormat>,
+
+    /// Sets the directory to which the tables are written.
+    #[arg(long, value_name = "DIR", default_value = "tables")]
+    out_dir: String,
+
+    /// Uses the first row of a table as its header when it has no header rows.
+    #[arg(long)]
+    first_row_header: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Outputs the form fields (content controls) of a DOCX file as JSON
+    Fields {
+        /// The input DOCX file, or - to read it from stdin
+        #[arg(value_name = "FILE")]
+        input: String,
+
+        /// Sets the output destination. Default is console.
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Pretty-prints the JSON
+        #[arg(short, long)]
+        pretty: bool,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
 
-    println!("File: {:?}", cli.input);
+    if let Some(Command::Fields {
+        input,
+        output,
+        pretty,
+    }) = cli.command
+    {
+        let markdown_doc = read_document(&input);
+        let result = match markdown_doc.form_fields_to_json(pretty) {
+            Ok(result) => result,
+            Err(err) => {
+                eprintln!("Could not serialize the form fields: {}", err);
+                std::process::exit(1);
+            }
+        };
+        write_output(output, result);
+        return;
+    }
+    let input = cli.input.unwrap_or_default();
+
+    eprintln!("File: {:?}", input);
 
     let output = match cli.output {
         Some(file) => file,
@@ -51,13 +157,96 @@ fn main() {
         std::process::exit(1);
     }
 
-    let mut input_file = cli.input.trim().to_string();
+    eprintln!("Output destination: {}", output);
+    eprintln!("Output format: {}", format);
 
-    if !input_file.to_lowercase().ends_with(".docx") {
+    let mut markdown_doc = read_document(&input);
+    if cli.revisions != RevisionMode::All {
+        markdown_doc.apply_revisions(cli.revisions);
+    }
+    if let Some(format) = cli.tables {
+        let options = TableExportOptions {
+            format,
+            first_row_as_header: cli.first_row_header,
+        };
+        match markdown_doc.export_tables(&cli.out_dir, &options) {
+            Ok(paths) => eprintln!("Exported {} tables to {:?}", paths.len(), cli.out_dir),
+            Err(err) => {
+                eprintln!("Could not export the tables: {}", err);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    let result = if format == "md" {
+        let options = MarkdownOptions {
+            revisions: cli.revisions,
+            comments: cli.comments,
+            headers_footers: cli.headers_footers,
+            heading_styles: cli.heading_style.into_iter().collect(),
+            style_map: cli
+                .style_map
+                .map(|style_map| read_style_map(&style_map))
+                .unwrap_or_default(),
+            custom_styles: cli.custom_styles,
+            formatting: cli.formatting,
+            fonts_and_colors: cli.fonts_and_colors,
+            hidden_text: cli.hidden_text,
+            underline: cli.underline,
+            table_format: cli.table_format,
+            merged_tables: cli.merged_tables,
+        };
+        if let Err(err) = markdown_doc.export_images() {
+            eprintln!("Could not export images: {}", err);
+        }
+        markdown_doc.to_markdown_with_options(&options)
+    } else if format == "json" {
+        markdown_doc.to_json(false)
+    } else {
+        markdown_doc.to_json(true)
+    };
+    write_output(Some(output), result);
+}
+
+fn parse_heading_style(heading_style: &str) -> Result<(String, usize), String> {
+    let (style, level) = heading_style
+        .rsplit_once('=')
+        .ok_or_else(|| format!("Expected STYLE=LEVEL, e.g. Chapter=1, but got {heading_style}"))?;
+    let level = level
+        .trim()
+        .parse()
+        .map_err(|_| format!("Unsupported heading level: {level}"))?;
+    Ok((style.trim().to_string(), level))
+}
+
+/// Read the style map file, and exit when that fails.
+fn read_style_map(path: &str) -> StyleMap {
+    let style_map = match fs::read_to_string(path) {
+        Ok(style_map) => style_map,
+        Err(err) => {
+            eprintln!("Could not read the style map {:?}: {}", path, err);
+            std::process::exit(1);
+        }
+    };
+    match style_map.parse() {
+        Ok(style_map) => style_map,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Read the DOCX file, or stdin when the input is -, and exit when that fails.
+fn read_document(input: &str) -> MarkdownDocument {
+    let mut input_file = input.trim().to_string();
+    let read_stdin = input_file == "-";
+
+    if !read_stdin && !input_file.to_lowercase().ends_with(".docx") {
         input_file = format!("{}.docx", input_file);
     }
 
-    if !file_exists_and_readable(&input_file) {
+    if !read_stdin && !file_exists_and_readable(&input_file) {
         eprintln!(
             "Input file does not exist or cannot be read: {:?}",
             input_file
@@ -65,22 +254,31 @@ fn main() {
         std::process::exit(1);
     }
 
-    println!("Processing file: {:?}", input_file);
-    println!("Output destination: {}", output);
-    println!("Output format: {}", format);
+    eprintln!("Processing file: {:?}", input_file);
 
-    let markdown_doc = MarkdownDocument::from_file(input_file);
-    let result = if format == "md" {
-        markdown_doc.to_markdown(true)
-    } else if format == "json" {
-        markdown_doc.to_json(false)
+    let markdown_doc = if read_stdin {
+        let mut bytes = Vec::new();
+        if let Err(err) = io::stdin().read_to_end(&mut bytes) {
+            eprintln!("Could not read from stdin: {}", err);
+            std::process::exit(1);
+        }
+        MarkdownDocument::from_bytes(&bytes)
     } else {
-        markdown_doc.to_json(true)
+        MarkdownDocument::try_from_file(&input_file)
     };
-    if output == "console" {
-        println!("{result}");
-    } else {
-        fs::write(output, result).expect("Could not write output");
+    match markdown_doc {
+        Ok(markdown_doc) => markdown_doc,
+        Err(err) => {
+            eprintln!("Could not process {:?}: {}", input_file, err);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn write_output(output: Option<String>, result: String) {
+    match output {
+        Some(file) if file != "console" => fs::write(file, result).expect("Could not write output"),
+        _ => println!("{result}"),
     }
 }
 
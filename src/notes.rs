@@ -0,0 +1,134 @@
+//! Footnotes and endnotes.
+use docx_rust::rels::Relationships;
+use hard_xml::XmlRead;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::wordml::{self, Parts};
+use crate::{
+    DocxParserError, MarkdownDocument, MarkdownOptions, MarkdownParagraph, TextBlock, TextType,
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum NoteKind {
+    Footnote,
+    Endnote,
+}
+
+impl NoteKind {
+    /// The kind of note that a reference block refers to.
+    pub(crate) fn of(block: &TextBlock) -> Option<NoteKind> {
+        match block.text_type {
+            TextType::FootnoteReference => Some(NoteKind::Footnote),
+            TextType::EndnoteReference => Some(NoteKind::Endnote),
+            _ => None,
+        }
+    }
+}
+
+/// A footnote or endnote
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Note {
+    pub kind: NoteKind,
+    pub id: String,
+    /// The label of the note in Markdown. Footnotes and endnotes are numbered together, in the
+    /// order in which they are first referenced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number: Option<usize>,
+    pub content: Vec<MarkdownParagraph>,
+}
+
+impl Note {
+    fn new(
+        kind: NoteKind,
+        id: &str,
+        content: &[wordml::Paragraph],
+        rels: Option<&Relationships>,
+    ) -> Self {
+        Note {
+            kind,
+            id: id.to_string(),
+            number: None,
+            content: content
+                .iter()
+                .map(|paragraph| MarkdownParagraph::from_paragraph(paragraph, rels))
+                .collect(),
+        }
+    }
+
+    /// Read all footnotes and endnotes of the document, leaving out the separators.
+    pub(crate) fn read_all(parts: &Parts) -> Result<Vec<Note>, DocxParserError> {
+        let mut notes = vec![];
+        if let Some(footnotes) = parts.get("word/footnotes.xml") {
+            let rels = parts.relationships("word/footnotes.xml")?;
+            for note in wordml::FootNotes::from_str(footnotes)?.notes {
+                if note.ty.is_none() {
+                    let kind = NoteKind::Footnote;
+                    notes.push(Note::new(kind, &note.id, &note.content, rels.as_ref()));
+                }
+            }
+        }
+        if let Some(endnotes) = parts.get("word/endnotes.xml") {
+            let rels = parts.relationships("word/endnotes.xml")?;
+            for note in wordml::EndNotes::from_str(endnotes)?.notes {
+                if note.ty.is_none() {
+                    let kind = NoteKind::Endnote;
+                    notes.push(Note::new(kind, &note.id, &note.content, rels.as_ref()));
+                }
+            }
+        }
+        Ok(notes)
+    }
+
+    /// Render the note as a footnote definition, or nothing when it is never referenced.
+    pub(crate) fn to_markdown(
+        &self,
+        doc: &MarkdownDocument,
+        options: &MarkdownOptions,
+    ) -> Option<String> {
+        let number = self.number?;
+        let body = self
+            .content
+            .iter()
+            .map(|paragraph| paragraph.to_markdown(&doc.styles, &mut HashMap::new(), doc, options))
+            .filter(|paragraph| !paragraph.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        // The reference mark at the start of the note is usually followed by a space
+        let body = body
+            .trim_start()
+            .lines()
+            .enumerate()
+            .map(|(i, line)| {
+                if i == 0 || line.is_empty() {
+                    line.to_string()
+                } else {
+                    format!("    {line}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        Some(format!("[^{number}]: {body}\n"))
+    }
+}
+
+/// Number the notes in the order in which they are first referenced.
+pub(crate) fn number_notes(markdown_doc: &mut MarkdownDocument) {
+    let references: Vec<(NoteKind, String)> = markdown_doc
+        .paragraphs()
+        .flat_map(|paragraph| paragraph.blocks.iter())
+        .filter_map(|block| Some((NoteKind::of(block)?, block.text.clone())))
+        .collect();
+    let mut number = 0;
+    for (kind, id) in references {
+        let note = markdown_doc
+            .notes
+            .iter_mut()
+            .find(|note| note.kind == kind && note.id == id && note.number.is_none());
+        if let Some(note) = note {
+            number += 1;
+            note.number = Some(number);
+        }
+    }
+}
@@ -0,0 +1,572 @@
+//! Rendering of numbers in the formats of Word lists, e.g. roman numerals, letters or words.
+use docx_rust::formatting::NumberFormat;
+
+const UPPER_LETTERS: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const LOWER_LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+const RUSSIAN_UPPER: &str = "АБВГДЕЖЗИКЛМНОПРСТУФХЦЧШЩЭЮЯ";
+const RUSSIAN_LOWER: &str = "абвгдежзиклмнопрстуфхцчшщэюя";
+const HEBREW_ALPHABET: &str = "אבגדהוזחטיכלמנסעפצקרשת";
+const ARABIC_ALPHA: &str = "أبتثجحخدذرزسشصضطظعغفقكلمنهوي";
+const ARABIC_ABJAD: &str = "أبجدهوزحطيكلمنسعفصقرشتثخذضظغ";
+const HINDI_VOWELS: &str = "अआइईउऊऋएऐओऔ";
+const HINDI_CONSONANTS: &str = "कखगघङचछजझञटठडढणतथदधनपफबभमयरलवशषसह";
+const THAI_LETTERS: &str = "กขคงจฉชซฌญฎฏฐฑฒณดตถทธนบปผฝพฟภมยรลวศษสหฬอฮ";
+const AIUEO: &str = "ｱｲｳｴｵｶｷｸｹｺｻｼｽｾｿﾀﾁﾂﾃﾄﾅﾆﾇﾈﾉﾊﾋﾌﾍﾎﾏﾐﾑﾒﾓﾔﾕﾖﾗﾘﾙﾚﾛﾜｦﾝ";
+const AIUEO_FULL_WIDTH: &str =
+    "アイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワヲン";
+const IROHA: &str = "ｲﾛﾊﾆﾎﾍﾄﾁﾘﾇﾙｦﾜｶﾖﾀﾚｿﾂﾈﾅﾗﾑｳヰﾉｵｸﾔﾏｹﾌｺｴﾃｱｻｷﾕﾒﾐｼヱﾋﾓｾｽ";
+const IROHA_FULL_WIDTH: &str = "イロハニホヘトチリヌルヲワカヨタレソツネナラムウヰノオクヤマケフコエテアサキユメミシヱヒモセス";
+const GANADA: &str = "가나다라마바사아자차카타파하";
+const CHOSUNG: &str = "ㄱㄴㄷㄹㅁㅂㅅㅇㅈㅊㅋㅌㅍㅎ";
+const CHICAGO: &str = "*†‡§";
+const HEAVENLY_STEMS: &str = "甲乙丙丁戊己庚辛壬癸";
+const EARTHLY_BRANCHES: &str = "子丑寅卯辰巳午未申酉戌亥";
+const IDEOGRAPH_DIGITS: &str = "〇一二三四五六七八九";
+const KOREAN_DIGITS: &str = "영일이삼사오육칠팔구";
+
+/// Render a number in a list number format, e.g. `XIV` for 14 in `NumberFormat::UpperRoman`.
+///
+/// Sequences of letters or symbols start over with doubled characters after the last one, e.g.
+/// `Z`, `AA`, `BB`. Numbers that a format cannot represent, like 0 in roman numerals or 21 in
+/// circled numbers, are rendered as decimals, and so are the Hindi counting system and the
+/// Korean, Vietnamese and Thai words above 99. Bullets and `NumberFormat::None` render as nothing.
+pub fn format_number(format: &NumberFormat, number: usize) -> String {
+    let formatted = match format {
+        NumberFormat::Decimal | NumberFormat::DecimalHalfWidth => Some(number.to_string()),
+        NumberFormat::DecimalZero if number < 10 => Some(format!("0{number}")),
+        NumberFormat::DecimalZero => Some(number.to_string()),
+        NumberFormat::UpperRoman => roman(number),
+        NumberFormat::LowerRoman => roman(number).map(|roman| roman.to_lowercase()),
+        NumberFormat::UpperLetter => alphabetic(UPPER_LETTERS, number),
+        NumberFormat::LowerLetter => alphabetic(LOWER_LETTERS, number),
+        NumberFormat::Ordinal => Some(ordinal(number)),
+        NumberFormat::CardinalText => Some(capitalize(&cardinal_text(number))),
+        NumberFormat::OrdinalText => Some(capitalize(&ordinal_text(number))),
+        NumberFormat::Hex => Some(format!("{number:X}")),
+        NumberFormat::Chicago => alphabetic(CHICAGO, number),
+        NumberFormat::NumberInDash => Some(format!("- {number} -")),
+        NumberFormat::DecimalFullWidth | NumberFormat::DecimalFullWidth2 => {
+            Some(digits("０１２３４５６７８９", number))
+        }
+        NumberFormat::DecimalEnclosedCircle | NumberFormat::DecimalEnclosedCircleChinese => {
+            enclosed('\u{2460}', 20, number)
+        }
+        NumberFormat::DecimalEnclosedFullstop => enclosed('\u{2488}', 20, number),
+        NumberFormat::DecimalEnclosedParen => enclosed('\u{2474}', 20, number),
+        NumberFormat::IdeographEnclosedCircle => enclosed('\u{3220}', 10, number),
+        NumberFormat::IdeographDigital
+        | NumberFormat::JapaneseDigitalTenThousand
+        | NumberFormat::TaiwaneseDigital
+        | NumberFormat::KoreanDigital2 => Some(digits(IDEOGRAPH_DIGITS, number)),
+        NumberFormat::KoreanDigital => Some(digits(KOREAN_DIGITS, number)),
+        NumberFormat::JapaneseCounting => counting(&JAPANESE, number),
+        NumberFormat::JapaneseLegal => counting(&JAPANESE_LEGAL, number),
+        NumberFormat::ChineseCounting | NumberFormat::ChineseCountingThousand => {
+            counting(&CHINESE, number)
+        }
+        NumberFormat::TaiwaneseCounting | NumberFormat::TaiwaneseCountingThousand => {
+            counting(&TAIWANESE, number)
+        }
+        NumberFormat::ChineseLegalSimplified => counting(&CHINESE_LEGAL, number),
+        NumberFormat::IdeographLegalTraditional => counting(&TAIWANESE_LEGAL, number),
+        NumberFormat::KoreanCounting => counting(&KOREAN, number),
+        NumberFormat::KoreanLegal => korean_legal(number),
+        NumberFormat::IdeographTraditional => nth(HEAVENLY_STEMS, number).map(String::from),
+        NumberFormat::IdeographZodiac => nth(EARTHLY_BRANCHES, number).map(String::from),
+        NumberFormat::IdeographZodiacTraditional if (1..=60).contains(&number) => {
+            let stem = nth(HEAVENLY_STEMS, (number - 1) % 10 + 1);
+            let branch = nth(EARTHLY_BRANCHES, (number - 1) % 12 + 1);
+            stem.zip(branch)
+                .map(|(stem, branch)| format!("{stem}{branch}"))
+        }
+        NumberFormat::IdeographZodiacTraditional => None,
+        NumberFormat::Aiueo => alphabetic(AIUEO, number),
+        NumberFormat::AiueoFullWidth => alphabetic(AIUEO_FULL_WIDTH, number),
+        NumberFormat::Iroha => alphabetic(IROHA, number),
+        NumberFormat::IrohaFullWidth => alphabetic(IROHA_FULL_WIDTH, number),
+        NumberFormat::Ganada => alphabetic(GANADA, number),
+        NumberFormat::Chosung => alphabetic(CHOSUNG, number),
+        NumberFormat::VietnameseCounting => vietnamese_counting(number),
+        NumberFormat::RussianUpper => alphabetic(RUSSIAN_UPPER, number),
+        NumberFormat::RussianLower => alphabetic(RUSSIAN_LOWER, number),
+        NumberFormat::Hebrew1 => hebrew_numeral(number),
+        NumberFormat::Hebrew2 => alphabetic(HEBREW_ALPHABET, number),
+        NumberFormat::ArabicAlpha => alphabetic(ARABIC_ALPHA, number),
+        NumberFormat::ArabicAbjad => alphabetic(ARABIC_ABJAD, number),
+        NumberFormat::HindiVowels => alphabetic(HINDI_VOWELS, number),
+        NumberFormat::HindiConsonants => alphabetic(HINDI_CONSONANTS, number),
+        NumberFormat::HindiNumbers | NumberFormat::HindiCounting => {
+            Some(digits("०१२३४५६७८९", number))
+        }
+        NumberFormat::ThaiLetters => alphabetic(THAI_LETTERS, number),
+        NumberFormat::ThaiNumbers => Some(digits("๐๑๒๓๔๕๖๗๘๙", number)),
+        NumberFormat::ThaiCounting => thai_counting(number),
+        NumberFormat::Bullet | NumberFormat::None => Some(String::new()),
+    };
+    formatted.unwrap_or_else(|| number.to_string())
+}
+
+/// The `number`th character of `characters`, counting from 1.
+fn nth(characters: &str, number: usize) -> Option<char> {
+    characters.chars().nth(number.checked_sub(1)?)
+}
+
+/// A character of the sequence, repeated once more for every time the sequence has been used.
+fn alphabetic(characters: &str, number: usize) -> Option<String> {
+    let index = number.checked_sub(1)?;
+    let length = characters.chars().count();
+    let character = nth(characters, index % length + 1)?;
+    Some(character.to_string().repeat(index / length + 1))
+}
+
+/// The decimal digits of the number, written with other characters for 0 to 9.
+fn digits(characters: &str, number: usize) -> String {
+    let characters: Vec<char> = characters.chars().collect();
+    number
+        .to_string()
+        .bytes()
+        .map(|digit| characters[(digit - b'0') as usize])
+        .collect()
+}
+
+/// A character of a range of enclosed numbers, like ① to ⑳, that starts at `first`.
+fn enclosed(first: char, last: usize, number: usize) -> Option<String> {
+    if !(1..=last).contains(&number) {
+        return None;
+    }
+    char::from_u32(first as u32 + number as u32 - 1).map(String::from)
+}
+
+fn roman(number: usize) -> Option<String> {
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    if number == 0 {
+        return None;
+    }
+    let mut rest = number;
+    let mut roman = String::new();
+    for (value, numeral) in NUMERALS {
+        while rest >= value {
+            roman += numeral;
+            rest -= value;
+        }
+    }
+    Some(roman)
+}
+
+fn ordinal(number: usize) -> String {
+    let suffix = match (number % 10, number % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{number}{suffix}")
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// The number in English words, e.g. `twenty-one`.
+fn cardinal_text(number: usize) -> String {
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+    const SCALES: [(usize, &str); 3] = [
+        (1_000_000_000, "billion"),
+        (1_000_000, "million"),
+        (1_000, "thousand"),
+    ];
+    for (scale, name) in SCALES {
+        if number >= scale {
+            let text = format!("{} {name}", cardinal_text(number / scale));
+            return match number % scale {
+                0 => text,
+                rest => format!("{text} {}", cardinal_text(rest)),
+            };
+        }
+    }
+    match number {
+        0..=19 => ONES[number].to_string(),
+        20..=99 => match number % 10 {
+            0 => TENS[number / 10].to_string(),
+            ones => format!("{}-{}", TENS[number / 10], ONES[ones]),
+        },
+        _ => match number % 100 {
+            0 => format!("{} hundred", ONES[number / 100]),
+            rest => format!("{} hundred {}", ONES[number / 100], cardinal_text(rest)),
+        },
+    }
+}
+
+/// The number in English words as an ordinal, e.g. `twenty-first`.
+fn ordinal_text(number: usize) -> String {
+    let cardinal = cardinal_text(number);
+    let split = cardinal.rfind([' ', '-']).map_or(0, |index| index + 1);
+    let (head, last) = cardinal.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        last => match last.strip_suffix('y') {
+            Some(stem) => format!("{stem}ieth"),
+            None => format!("{last}th"),
+        },
+    };
+    format!("{head}{last}")
+}
+
+/// A counting system with characters for the digits and for tens, hundreds, thousands and ten
+/// thousands, as used in Chinese, Japanese and Korean.
+struct Counting {
+    digits: &'static str,
+    /// The characters for ten, a hundred and a thousand
+    units: [char; 3],
+    ten_thousand: char,
+    /// Leave out the one before ten, a hundred or a thousand, e.g. 十 instead of 一十
+    omit_one: OmitOne,
+    /// Write a zero for skipped digits, e.g. 一百〇五 for 105
+    zero: bool,
+}
+
+#[derive(PartialEq)]
+enum OmitOne {
+    Never,
+    /// Only before a ten at the start, e.g. 十一 for 11 but 一百一十 for 110
+    LeadingTen,
+    Always,
+}
+
+const JAPANESE: Counting = Counting {
+    digits: IDEOGRAPH_DIGITS,
+    units: ['十', '百', '千'],
+    ten_thousand: '万',
+    omit_one: OmitOne::Always,
+    zero: false,
+};
+const JAPANESE_LEGAL: Counting = Counting {
+    digits: "〇壱弐参四伍六七八九",
+    units: ['拾', '百', '阡'],
+    ten_thousand: '萬',
+    omit_one: OmitOne::Never,
+    zero: false,
+};
+const CHINESE: Counting = Counting {
+    digits: IDEOGRAPH_DIGITS,
+    units: ['十', '百', '千'],
+    ten_thousand: '万',
+    omit_one: OmitOne::LeadingTen,
+    zero: true,
+};
+const TAIWANESE: Counting = Counting {
+    digits: IDEOGRAPH_DIGITS,
+    units: ['十', '百', '千'],
+    ten_thousand: '萬',
+    omit_one: OmitOne::LeadingTen,
+    zero: true,
+};
+const CHINESE_LEGAL: Counting = Counting {
+    digits: "零壹贰叁肆伍陆柒捌玖",
+    units: ['拾', '佰', '仟'],
+    ten_thousand: '万',
+    omit_one: OmitOne::Never,
+    zero: true,
+};
+const TAIWANESE_LEGAL: Counting = Counting {
+    digits: "零壹貳參肆伍陸柒捌玖",
+    units: ['拾', '佰', '仟'],
+    ten_thousand: '萬',
+    omit_one: OmitOne::Never,
+    zero: true,
+};
+const KOREAN: Counting = Counting {
+    digits: KOREAN_DIGITS,
+    units: ['십', '백', '천'],
+    ten_thousand: '만',
+    omit_one: OmitOne::Always,
+    zero: false,
+};
+
+fn counting(system: &Counting, number: usize) -> Option<String> {
+    if number == 0 || number >= 100_000_000 {
+        return None;
+    }
+    let digits: Vec<char> = system.digits.chars().collect();
+    let below_ten_thousand = |number: usize, leading: bool| {
+        let mut text = String::new();
+        let mut skipped = false;
+        for position in (0..4).rev() {
+            let digit = number / 10usize.pow(position) % 10;
+            if digit == 0 {
+                skipped = !text.is_empty();
+                continue;
+            }
+            if skipped && system.zero {
+                text.push(digits[0]);
+            }
+            skipped = false;
+            let omit_one = digit == 1
+                && position > 0
+                && match system.omit_one {
+                    OmitOne::Never => false,
+                    OmitOne::LeadingTen => position == 1 && leading && text.is_empty(),
+                    OmitOne::Always => true,
+                };
+            if !omit_one {
+                text.push(digits[digit]);
+            }
+            if position > 0 {
+                text.push(system.units[position as usize - 1]);
+            }
+        }
+        text
+    };
+    let (high, low) = (number / 10_000, number % 10_000);
+    if high == 0 {
+        return Some(below_ten_thousand(low, true));
+    }
+    let mut text = below_ten_thousand(high, true);
+    text.push(system.ten_thousand);
+    if low > 0 {
+        if low < 1000 && system.zero {
+            text.push(digits[0]);
+        }
+        text += &below_ten_thousand(low, false);
+    }
+    Some(text)
+}
+
+/// Native Korean numbers, e.g. 스물하나 for 21.
+fn korean_legal(number: usize) -> Option<String> {
+    const ONES: [&str; 10] = [
+        "", "하나", "둘", "셋", "넷", "다섯", "여섯", "일곱", "여덟", "아홉",
+    ];
+    const TENS: [&str; 10] = [
+        "", "열", "스물", "서른", "마흔", "쉰", "예순", "일흔", "여든", "아흔",
+    ];
+    match number {
+        1..=99 => Some(format!("{}{}", TENS[number / 10], ONES[number % 10])),
+        _ => None,
+    }
+}
+
+fn vietnamese_counting(number: usize) -> Option<String> {
+    const ONES: [&str; 10] = [
+        "", "một", "hai", "ba", "bốn", "năm", "sáu", "bảy", "tám", "chín",
+    ];
+    let (tens, ones) = (number / 10, number % 10);
+    let text = match (tens, ones) {
+        (0, 1..=9) => ONES[ones].to_string(),
+        (1, 0) => "mười".to_string(),
+        (1, 5) => "mười lăm".to_string(),
+        (1, _) => format!("mười {}", ONES[ones]),
+        (2..=9, 0) => format!("{} mươi", ONES[tens]),
+        (2..=9, 1) => format!("{} mươi mốt", ONES[tens]),
+        (2..=9, 4) => format!("{} mươi tư", ONES[tens]),
+        (2..=9, 5) => format!("{} mươi lăm", ONES[tens]),
+        (2..=9, _) => format!("{} mươi {}", ONES[tens], ONES[ones]),
+        _ => return None,
+    };
+    Some(text)
+}
+
+fn thai_counting(number: usize) -> Option<String> {
+    const DIGITS: [&str; 10] = [
+        "ศูนย์",
+        "หนึ่ง",
+        "สอง",
+        "สาม",
+        "สี่",
+        "ห้า",
+        "หก",
+        "เจ็ด",
+        "แปด",
+        "เก้า",
+    ];
+    let (tens, ones) = (number / 10, number % 10);
+    let tens_text = match tens {
+        0 => String::new(),
+        1 => "สิบ".to_string(),
+        2 => "ยี่สิบ".to_string(),
+        3..=9 => format!("{}สิบ", DIGITS[tens]),
+        _ => return None,
+    };
+    let ones_text = match ones {
+        0 if tens == 0 => return None,
+        0 => "",
+        1 if tens > 0 => "เอ็ด",
+        _ => DIGITS[ones],
+    };
+    Some(format!("{tens_text}{ones_text}"))
+}
+
+/// Hebrew numerals, in which 15 and 16 are written as 9 + 6 and 9 + 7.
+fn hebrew_numeral(number: usize) -> Option<String> {
+    const ONES: [char; 9] = ['א', 'ב', 'ג', 'ד', 'ה', 'ו', 'ז', 'ח', 'ט'];
+    const TENS: [char; 9] = ['י', 'כ', 'ל', 'מ', 'נ', 'ס', 'ע', 'פ', 'צ'];
+    const HUNDREDS: [char; 4] = ['ק', 'ר', 'ש', 'ת'];
+    if number == 0 || number >= 1000 {
+        return None;
+    }
+    let mut text = String::new();
+    let mut hundreds = number / 100;
+    while hundreds >= 4 {
+        text.push(HUNDREDS[3]);
+        hundreds -= 4;
+    }
+    if hundreds > 0 {
+        text.push(HUNDREDS[hundreds - 1]);
+    }
+    match number % 100 {
+        15 => text += "טו",
+        16 => text += "טז",
+        rest => {
+            if rest >= 10 {
+                text.push(TENS[rest / 10 - 1]);
+            }
+            if rest % 10 > 0 {
+                text.push(ONES[rest % 10 - 1]);
+            }
+        }
+    }
+    Some(text)
+}
+
+#[test]
+fn test_format_number() {
+    let formatted = |format: NumberFormat, numbers: &[usize]| {
+        numbers
+            .iter()
+            .map(|number| format_number(&format, *number))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    assert_eq!(
+        "I II IV IX XIV XL MCMXCIX",
+        formatted(NumberFormat::UpperRoman, &[1, 2, 4, 9, 14, 40, 1999])
+    );
+    assert_eq!("iii xlii", formatted(NumberFormat::LowerRoman, &[3, 42]));
+    assert_eq!(
+        "A Z AA BB ZZ AAA",
+        formatted(NumberFormat::UpperLetter, &[1, 26, 27, 28, 52, 53])
+    );
+    assert_eq!(
+        "01 09 10",
+        formatted(NumberFormat::DecimalZero, &[1, 9, 10])
+    );
+    assert_eq!(
+        "1st 2nd 3rd 4th 11th 12th 13th 21st 102nd",
+        formatted(NumberFormat::Ordinal, &[1, 2, 3, 4, 11, 12, 13, 21, 102])
+    );
+    assert_eq!(
+        "One Twelve Twenty-one One hundred five",
+        formatted(NumberFormat::CardinalText, &[1, 12, 21, 105])
+    );
+    assert_eq!(
+        "First Twelfth Twentieth Twenty-second One thousand third",
+        formatted(NumberFormat::OrdinalText, &[1, 12, 20, 22, 1003])
+    );
+    assert_eq!(
+        "* † ‡ § **",
+        formatted(NumberFormat::Chicago, &[1, 2, 3, 4, 5])
+    );
+    assert_eq!(
+        "① ⑳ 21",
+        formatted(NumberFormat::DecimalEnclosedCircle, &[1, 20, 21])
+    );
+    assert_eq!(
+        "一 十 十一 二十 百五 二千三十",
+        formatted(NumberFormat::JapaneseCounting, &[1, 10, 11, 20, 105, 2030])
+    );
+    assert_eq!(
+        "十 一百〇五 一百一十 一万〇一",
+        formatted(NumberFormat::ChineseCounting, &[10, 105, 110, 10001])
+    );
+    assert_eq!(
+        "壹拾 贰佰零伍",
+        formatted(NumberFormat::ChineseLegalSimplified, &[10, 205])
+    );
+    assert_eq!(
+        "二〇二四",
+        formatted(NumberFormat::IdeographDigital, &[2024])
+    );
+    assert_eq!(
+        "甲子 癸亥",
+        formatted(NumberFormat::IdeographZodiacTraditional, &[1, 60])
+    );
+    assert_eq!("가 하 가가", formatted(NumberFormat::Ganada, &[1, 14, 15]));
+    assert_eq!(
+        "십 이십일 스물하나",
+        [
+            formatted(NumberFormat::KoreanCounting, &[10, 21]),
+            formatted(NumberFormat::KoreanLegal, &[21])
+        ]
+        .join(" ")
+    );
+    assert_eq!(
+        "א י טו טז קכג",
+        formatted(NumberFormat::Hebrew1, &[1, 10, 15, 16, 123])
+    );
+    assert_eq!("أ ب ت", formatted(NumberFormat::ArabicAlpha, &[1, 2, 3]));
+    assert_eq!("أ ب ج", formatted(NumberFormat::ArabicAbjad, &[1, 2, 3]));
+    assert_eq!(
+        "а я аа",
+        formatted(NumberFormat::RussianLower, &[1, 28, 29])
+    );
+    assert_eq!(
+        "mười lăm hai mươi mốt",
+        formatted(NumberFormat::VietnameseCounting, &[15, 21])
+    );
+    assert_eq!(
+        "สิบเอ็ด ยี่สิบ",
+        formatted(NumberFormat::ThaiCounting, &[11, 20])
+    );
+    assert_eq!("１２", format_number(&NumberFormat::DecimalFullWidth, 12));
+    assert_eq!("७", format_number(&NumberFormat::HindiNumbers, 7));
+    assert_eq!("", format_number(&NumberFormat::None, 3));
+}
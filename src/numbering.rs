@@ -0,0 +1,195 @@
+//! Numbered and bulleted lists, with the definition of each of their levels.
+use docx_rust::formatting::NumberFormat;
+use hard_xml::XmlRead;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::wordml::{self, Parts};
+use crate::{format_number, DocxParserError};
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownNumbering {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<isize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub indent_level: Option<isize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>, // NumberFormat
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level_text: Option<String>,
+    /// The definition of each level of the list, including the overrides of this list
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub levels: Vec<MarkdownNumberingLevel>,
+}
+
+/// A level of a list
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownNumberingLevel {
+    pub level: isize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>, // NumberFormat
+    /// The pattern of the number, in which `%1` is replaced by the number of the first level,
+    /// `%2` by that of the second level, etc., e.g. `%1.%2.`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub level_text: Option<String>,
+    pub start: usize,
+    /// The level is restarted after a level up to this one (counting from 1) is used, or never
+    /// when it is 0. Without it, the level is restarted after any of the levels above it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub restart: Option<isize>,
+    /// Show the numbers of all levels as decimals, e.g. for legal documents
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_legal: bool,
+}
+
+impl From<&wordml::Level<'_>> for MarkdownNumberingLevel {
+    fn from(level: &wordml::Level) -> Self {
+        MarkdownNumberingLevel {
+            level: level.i_level.unwrap_or_default(),
+            format: level
+                .number_format
+                .as_ref()
+                .map(|format| format.value.to_string()),
+            level_text: level.level_text.as_ref().map(|text| text.value.to_string()),
+            start: level
+                .start
+                .as_ref()
+                .and_then(|start| start.value)
+                .map_or(1, |start| start.max(0) as usize),
+            restart: level.restart.as_ref().and_then(|restart| restart.value),
+            is_legal: level.is_legal.is_some(),
+        }
+    }
+}
+
+impl MarkdownNumberingLevel {
+    fn number_format(&self) -> NumberFormat {
+        match &self.format {
+            Some(format) => NumberFormat::from_str(format).unwrap_or(NumberFormat::Decimal),
+            None => NumberFormat::Decimal,
+        }
+    }
+
+    /// Whether this level is restarted when `level` is used.
+    fn restarts_after(&self, level: isize) -> bool {
+        level < self.level
+            && match self.restart {
+                None => true,
+                Some(restart) => level < restart,
+            }
+    }
+}
+
+impl MarkdownNumbering {
+    /// Read the lists of the document, keyed by their id, with the level overrides of each list
+    /// applied to the levels of its abstract numbering.
+    pub(crate) fn read_all(parts: &Parts) -> Result<HashMap<isize, Self>, DocxParserError> {
+        let mut numberings = HashMap::new();
+        let Some(numbering) = parts.get("word/numbering.xml") else {
+            return Ok(numberings);
+        };
+        let numbering = wordml::Numbering::from_str(numbering)?;
+        for num in &numbering.numberings {
+            let Some(id) = num.num_id else {
+                continue;
+            };
+            let abstract_num = numbering.abstract_numberings.iter().find(|abstract_num| {
+                abstract_num.abstract_num_id == num.abstract_num_id.as_ref().and_then(|id| id.value)
+            });
+            let Some(abstract_num) = abstract_num else {
+                continue;
+            };
+            let mut levels: Vec<MarkdownNumberingLevel> =
+                abstract_num.levels.iter().map(Into::into).collect();
+            for level_override in &num.level_overrides {
+                let i_level = level_override.i_level.unwrap_or_default();
+                if let Some(level) = &level_override.level {
+                    let mut level: MarkdownNumberingLevel = level.into();
+                    level.level = i_level;
+                    levels.retain(|level| level.level != i_level);
+                    levels.push(level);
+                }
+                if let Some(start) = level_override
+                    .start_override
+                    .as_ref()
+                    .and_then(|start| start.value)
+                {
+                    if let Some(level) = levels.iter_mut().find(|level| level.level == i_level) {
+                        level.start = start.max(0) as usize;
+                    }
+                }
+            }
+            levels.sort_by_key(|level| level.level);
+            let first = levels.first();
+            let numbering = MarkdownNumbering {
+                id: Some(id),
+                indent_level: None,
+                format: first.and_then(|level| level.format.clone()),
+                level_text: first.and_then(|level| level.level_text.clone()),
+                levels,
+            };
+            numberings.insert(id, numbering);
+        }
+        Ok(numberings)
+    }
+
+    pub fn level(&self, level: isize) -> Option<&MarkdownNumberingLevel> {
+        self.levels
+            .iter()
+            .find(|definition| definition.level == level)
+    }
+
+    /// Count the next item at `level` of the list and return its marker. `counters` holds the
+    /// last number used for each (list id, level).
+    pub(crate) fn next_marker(
+        &self,
+        level: isize,
+        counters: &mut HashMap<(isize, isize), usize>,
+    ) -> String {
+        let id = self.id.unwrap_or_default();
+        counters.retain(|(counter_id, counter_level), _| {
+            *counter_id != id
+                || self
+                    .level(*counter_level)
+                    .map_or(*counter_level <= level, |other| {
+                        !other.restarts_after(level)
+                    })
+        });
+        let definition = self.level(level);
+        let start = definition.map_or(1, |definition| definition.start);
+        counters
+            .entry((id, level))
+            .and_modify(|count| *count += 1)
+            .or_insert(start);
+
+        let count = counters[&(id, level)];
+        let format = definition.map_or(NumberFormat::Decimal, |definition| {
+            definition.number_format()
+        });
+        // Markdown only numbers lists with digits, and Pandoc's fancy lists with letters and
+        // roman numerals, so the level text and the other number formats are left out
+        match format {
+            NumberFormat::Bullet => {
+                match definition.and_then(|definition| definition.level_text.as_ref()) {
+                    Some(level_text) if level_text.trim().is_empty() => " ".to_string(),
+                    _ => "-".to_string(),
+                }
+            }
+            NumberFormat::Decimal
+            | NumberFormat::DecimalZero
+            | NumberFormat::LowerLetter
+            | NumberFormat::LowerRoman => format!("{}.", format_number(&format, count)),
+            // Pandoc needs two spaces after a single capital letter, which could be an initial
+            NumberFormat::UpperLetter | NumberFormat::UpperRoman => {
+                match format_number(&format, count) {
+                    letter if letter.len() == 1 => format!("{letter}. "),
+                    letters => format!("{letters}."),
+                }
+            }
+            _ => format!("{count}."),
+        }
+    }
+}
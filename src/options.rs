@@ -0,0 +1,288 @@
+use crate::StyleMap;
+use std::collections::HashMap;
+
+/// Options for converting a [`crate::MarkdownDocument`] to Markdown.
+#[derive(Debug, Default, Clone)]
+pub struct MarkdownOptions {
+    /// How to render tracked changes
+    pub revisions: RevisionMode,
+    /// How to render comments
+    pub comments: CommentStyle,
+    /// How to render page headers and footers
+    pub headers_footers: HeaderFooterStyle,
+    /// Heading levels for paragraph styles, by the name or id of the style (ignoring case), e.g.
+    /// `"Chapter" => 1`. They take precedence over the outline level and the built-in heading
+    /// styles, and a level of 0 turns a heading style into a plain paragraph.
+    pub heading_styles: HashMap<String, usize>,
+    /// Markdown constructs for paragraph and character styles, which take precedence over the
+    /// built-in rules
+    pub style_map: StyleMap,
+    /// Keep the custom styles of paragraphs and runs that are not mapped to a construct, as
+    /// pandoc fenced divs and spans with a `custom-style` attribute
+    pub custom_styles: bool,
+    /// How to render superscript, subscript, highlight, small caps and all caps
+    pub formatting: FormattingStyle,
+    /// Keep the font and color of runs, as CSS in pandoc spans or HTML
+    pub fonts_and_colors: bool,
+    /// Keep hidden text
+    pub hidden_text: bool,
+    /// How to render underlined text
+    pub underline: UnderlineStyle,
+    /// How to render tables
+    pub table_format: TableFormat,
+    /// How to render tables that the table format cannot represent: tables with cells that span
+    /// several columns or rows or that hold nested tables, tables with cells of several paragraphs
+    /// for pipe, simple and multiline tables, and tables without a header row for pipe tables
+    pub merged_tables: MergedTableStyle,
+}
+
+/// How to handle tracked changes (insertions, deletions and moves).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionMode {
+    /// Accept all changes, i.e. show the final text
+    #[default]
+    Accept,
+    /// Reject all changes, i.e. show the original text
+    Reject,
+    /// Show all changes, as CriticMarkup in Markdown
+    All,
+}
+
+impl std::str::FromStr for RevisionMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "accept" => Ok(RevisionMode::Accept),
+            "reject" => Ok(RevisionMode::Reject),
+            "all" => Ok(RevisionMode::All),
+            _ => Err(format!(
+                "Unsupported revision mode: {s}. Supported modes are accept, reject and all."
+            )),
+        }
+    }
+}
+
+/// How to render comments in Markdown.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CommentStyle {
+    /// Leave comments out
+    #[default]
+    None,
+    /// Highlight the commented text and add the comment in CriticMarkup, e.g. `{==text==}{>>comment<<}`
+    CriticMarkup,
+    /// Wrap the commented text in `comment-start` and `comment-end` HTML spans, like pandoc does
+    Html,
+    /// Add the comment as a footnote at the end of the commented text
+    Footnotes,
+}
+
+impl std::str::FromStr for CommentStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(CommentStyle::None),
+            "criticmarkup" => Ok(CommentStyle::CriticMarkup),
+            "html" => Ok(CommentStyle::Html),
+            "footnotes" => Ok(CommentStyle::Footnotes),
+            _ => Err(format!(
+                "Unsupported comment style: {s}. Supported styles are none, criticmarkup, html and footnotes."
+            )),
+        }
+    }
+}
+
+/// How to render the page headers and footers in Markdown. The default header of the first
+/// section and the default footer of the last section are used, or the first-page header and
+/// footer when a section has no default one.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum HeaderFooterStyle {
+    /// Leave headers and footers out
+    #[default]
+    None,
+    /// Render the header as the first block and the footer as the last block
+    Blocks,
+    /// Add the header and footer text as `header` and `footer` to a YAML front matter block
+    FrontMatter,
+}
+
+impl std::str::FromStr for HeaderFooterStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(HeaderFooterStyle::None),
+            "blocks" => Ok(HeaderFooterStyle::Blocks),
+            "front-matter" | "frontmatter" => Ok(HeaderFooterStyle::FrontMatter),
+            _ => Err(format!(
+                "Unsupported header and footer style: {s}. Supported styles are none, blocks and front-matter."
+            )),
+        }
+    }
+}
+
+/// How to render the formatting of runs that basic Markdown has no syntax for: superscript,
+/// subscript, highlight, small caps and all caps.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FormattingStyle {
+    /// Leave the formatting out
+    None,
+    /// Use extended Markdown, like pandoc: `^superscript^`, `~subscript~`, `==highlight==` and
+    /// `[Small Caps]{.smallcaps}`, and text in all caps in capitals
+    #[default]
+    Markdown,
+    /// Use HTML: `<sup>`, `<sub>`, `<mark>` and a small caps `<span>`, and text in all caps in
+    /// capitals
+    Html,
+}
+
+impl std::str::FromStr for FormattingStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(FormattingStyle::None),
+            "markdown" | "md" => Ok(FormattingStyle::Markdown),
+            "html" => Ok(FormattingStyle::Html),
+            _ => Err(format!(
+                "Unsupported formatting style: {s}. Supported styles are none, markdown and html."
+            )),
+        }
+    }
+}
+
+/// How to render underlined text, for which Markdown has no syntax: `__text__` is bold.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UnderlineStyle {
+    /// Leave the underline out
+    Ignore,
+    /// Use HTML, `<u>text</u>`
+    Html,
+    /// Use a pandoc span, `[text]{.underline}`
+    #[default]
+    Pandoc,
+}
+
+impl std::str::FromStr for UnderlineStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ignore" | "none" => Ok(UnderlineStyle::Ignore),
+            "html" => Ok(UnderlineStyle::Html),
+            "pandoc" => Ok(UnderlineStyle::Pandoc),
+            _ => Err(format!(
+                "Unsupported underline style: {s}. Supported styles are ignore, html and pandoc."
+            )),
+        }
+    }
+}
+
+/// How to render tables.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    /// A pipe table, with a header row and a single paragraph per cell
+    #[default]
+    Pipe,
+    /// A pandoc grid table, in which cells hold Markdown blocks
+    Grid,
+    /// A pandoc simple table, with a single line per row
+    Simple,
+    /// A pandoc multiline table, in which long cells wrap over several lines
+    Multiline,
+    /// An HTML table
+    Html,
+    /// Leave tables out
+    Skip,
+}
+
+impl std::str::FromStr for TableFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "pipe" => Ok(TableFormat::Pipe),
+            "grid" => Ok(TableFormat::Grid),
+            "simple" => Ok(TableFormat::Simple),
+            "multiline" => Ok(TableFormat::Multiline),
+            "html" => Ok(TableFormat::Html),
+            "skip" | "none" => Ok(TableFormat::Skip),
+            _ => Err(format!(
+                "Unsupported table format: {s}. Supported formats are pipe, grid, simple, multiline, html and skip."
+            )),
+        }
+    }
+}
+
+/// How to render tables that the table format cannot represent, like tables with merged cells.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum MergedTableStyle {
+    /// An HTML table, with `colspan` and `rowspan` attributes
+    #[default]
+    Html,
+    /// A pandoc grid table, in which cells can span columns and rows
+    Grid,
+}
+
+impl std::str::FromStr for MergedTableStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "html" => Ok(MergedTableStyle::Html),
+            "grid" => Ok(MergedTableStyle::Grid),
+            _ => Err(format!(
+                "Unsupported merged table style: {s}. Supported styles are html and grid."
+            )),
+        }
+    }
+}
+
+/// Options for exporting the data in the tables of a [`crate::MarkdownDocument`].
+#[derive(Debug, Default, Clone)]
+pub struct TableExportOptions {
+    /// The file format of each table
+    pub format: TableExportFormat,
+    /// Use the first row of a table as its header when the table has no header rows
+    pub first_row_as_header: bool,
+}
+
+/// The file format of exported tables.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TableExportFormat {
+    /// Comma-separated values with CRLF line endings, in which fields with a comma, quote or line
+    /// break are quoted
+    #[default]
+    Csv,
+    /// Tab-separated values, in which tabs and line breaks in fields become spaces
+    Tsv,
+    /// An array of JSON records, keyed by the header row
+    Json,
+}
+
+impl TableExportFormat {
+    /// The file extension of the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TableExportFormat::Csv => "csv",
+            TableExportFormat::Tsv => "tsv",
+            TableExportFormat::Json => "json",
+        }
+    }
+}
+
+impl std::str::FromStr for TableExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(TableExportFormat::Csv),
+            "tsv" => Ok(TableExportFormat::Tsv),
+            "json" => Ok(TableExportFormat::Json),
+            _ => Err(format!(
+                "Unsupported table export format: {s}. Supported formats are csv, tsv and json."
+            )),
+        }
+    }
+}
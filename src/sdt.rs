@@ -0,0 +1,310 @@
+//! Structured document tags, better known as content controls.
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::collections::HashSet;
+
+use crate::utils::unique_key;
+use crate::wordml;
+use crate::{MarkdownContent, MarkdownParagraph, RevisionMode, TextType};
+
+/// The type of a content control
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum SdtType {
+    RichText,
+    Text,
+    DropDownList,
+    ComboBox,
+    Date,
+    CheckBox,
+    Picture,
+    /// Building block, e.g. a table of contents or cover page
+    DocPartObj,
+    DocPartList,
+    Group,
+    Citation,
+    Bibliography,
+    Equation,
+}
+
+/// Identifies a content control
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SdtProperties {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    #[serde(rename = "type")]
+    pub sdt_type: SdtType,
+    /// The building block gallery, e.g. `Table of Contents`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gallery: Option<String>,
+    /// The control shows its placeholder text, i.e. it has no value
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub showing_placeholder: bool,
+    /// The state of a checkbox
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checked: Option<bool>,
+    /// The selected date of a date picker, e.g. `2024-01-31T00:00:00Z`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+}
+
+impl<'a> From<&wordml::SdtProperty<'a>> for SdtProperties {
+    fn from(property: &wordml::SdtProperty<'a>) -> Self {
+        let sdt_type = if property.text.is_some() {
+            SdtType::Text
+        } else if property.drop_down_list.is_some() {
+            SdtType::DropDownList
+        } else if property.combo_box.is_some() {
+            SdtType::ComboBox
+        } else if property.date.is_some() {
+            SdtType::Date
+        } else if property.checkbox.is_some() {
+            SdtType::CheckBox
+        } else if property.picture.is_some() {
+            SdtType::Picture
+        } else if property.doc_part_obj.is_some() {
+            SdtType::DocPartObj
+        } else if property.doc_part_list.is_some() {
+            SdtType::DocPartList
+        } else if property.group.is_some() {
+            SdtType::Group
+        } else if property.citation.is_some() {
+            SdtType::Citation
+        } else if property.bibliography.is_some() {
+            SdtType::Bibliography
+        } else if property.equation.is_some() {
+            SdtType::Equation
+        } else {
+            SdtType::RichText
+        };
+        let gallery = property
+            .doc_part_obj
+            .as_ref()
+            .or(property.doc_part_list.as_ref())
+            .and_then(|doc_part| doc_part.doc_part_gallery.as_ref())
+            .and_then(|gallery| gallery.name.as_ref());
+        SdtProperties {
+            id: property
+                .id
+                .as_ref()
+                .and_then(|id| id.value.as_ref())
+                .map(|id| id.to_string()),
+            tag: property
+                .tag
+                .as_ref()
+                .and_then(|tag| tag.value.as_ref())
+                .map(|tag| tag.to_string()),
+            alias: property
+                .alias
+                .as_ref()
+                .and_then(|alias| alias.value.as_ref())
+                .map(|alias| alias.to_string()),
+            sdt_type,
+            gallery: gallery.map(|gallery| gallery.to_string()),
+            showing_placeholder: property.showing_placeholder.is_some(),
+            checked: property.checkbox.as_ref().map(|checkbox| {
+                checkbox
+                    .checked
+                    .as_ref()
+                    .and_then(|checked| checked.value.as_deref())
+                    .is_some_and(|value| matches!(value, "1" | "true" | "on"))
+            }),
+            date: property
+                .date
+                .as_ref()
+                .and_then(|date| date.full_date.as_ref())
+                .map(|date| date.to_string()),
+        }
+    }
+}
+
+/// A block-level content control, with the paragraphs and tables inside it
+#[derive(Debug, Clone, Serialize)]
+pub struct MarkdownSdt {
+    #[serde(flatten)]
+    pub properties: SdtProperties,
+    pub content: Vec<MarkdownContent>,
+}
+
+/// The value of a form field
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(untagged)]
+pub enum FieldValue {
+    Text(String),
+    Checked(bool),
+}
+
+/// A content control that is filled in as part of a form: a plain or rich text control, a
+/// dropdown list or combo box, a date picker or a checkbox.
+#[derive(Debug, PartialEq, Eq, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FormField {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    #[serde(rename = "type")]
+    pub field_type: SdtType,
+    /// The text of text controls and lists, the date of date pickers (e.g. `2024-01-31`) and the
+    /// state of checkboxes. `None` when the control still shows its placeholder text.
+    pub value: Option<FieldValue>,
+}
+
+impl FormField {
+    /// The field for a content control with the given text, if it is a form field with a name.
+    fn new(properties: &SdtProperties, text: String) -> Option<FormField> {
+        let is_form_field = matches!(
+            properties.sdt_type,
+            SdtType::RichText
+                | SdtType::Text
+                | SdtType::DropDownList
+                | SdtType::ComboBox
+                | SdtType::Date
+                | SdtType::CheckBox
+        );
+        if !is_form_field || (properties.tag.is_none() && properties.alias.is_none()) {
+            return None;
+        }
+        let value = if properties.showing_placeholder {
+            None
+        } else {
+            Some(match properties.sdt_type {
+                SdtType::CheckBox => FieldValue::Checked(properties.checked.unwrap_or_default()),
+                SdtType::Date => match &properties.date {
+                    Some(date) => FieldValue::Text(date.split('T').next().unwrap_or(date).into()),
+                    None => FieldValue::Text(text),
+                },
+                _ => FieldValue::Text(text),
+            })
+        };
+        Some(FormField {
+            tag: properties.tag.clone(),
+            alias: properties.alias.clone(),
+            field_type: properties.sdt_type,
+            value,
+        })
+    }
+
+    /// The name of the field: its tag, or its alias when it has no tag.
+    pub fn name(&self) -> &str {
+        self.tag
+            .as_deref()
+            .or(self.alias.as_deref())
+            .unwrap_or_default()
+    }
+
+    /// Collect the form fields in the content, in reading order.
+    pub(crate) fn collect(content: &[MarkdownContent], fields: &mut Vec<FormField>) {
+        for content in content {
+            match content {
+                MarkdownContent::Paragraph(paragraph) => {
+                    FormField::collect_inline(std::iter::once(paragraph), fields)
+                }
+                MarkdownContent::Table(table) => {
+                    for cell in table.iter().flat_map(|row| row.cells.iter()) {
+                        FormField::collect(&cell.content, fields)
+                    }
+                }
+                MarkdownContent::Sdt(sdt) => {
+                    let text = sdt
+                        .content
+                        .iter()
+                        .flat_map(MarkdownContent::paragraphs)
+                        .map(plain_text)
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    match FormField::new(&sdt.properties, text) {
+                        Some(field) => fields.push(field),
+                        None => FormField::collect(&sdt.content, fields),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Collect the fields of inline content controls.
+    fn collect_inline<'a>(
+        paragraphs: impl Iterator<Item = &'a MarkdownParagraph>,
+        fields: &mut Vec<FormField>,
+    ) {
+        let mut current: Option<(&SdtProperties, String)> = None;
+        let mut flush = |current: &mut Option<(&SdtProperties, String)>| {
+            if let Some((properties, text)) = current.take() {
+                fields.extend(FormField::new(properties, text));
+            }
+        };
+        for paragraph in paragraphs {
+            let mut new_paragraph = true;
+            for block in &paragraph.blocks {
+                if block.text_type != TextType::Text
+                    || !RevisionMode::Accept.shows(block.revision.as_ref())
+                {
+                    continue;
+                }
+                match (&mut current, &block.sdt) {
+                    (Some((properties, text)), Some(sdt)) if *properties == sdt => {
+                        if new_paragraph {
+                            text.push('\n');
+                        }
+                        text.push_str(&block.text);
+                    }
+                    (_, Some(sdt)) => {
+                        flush(&mut current);
+                        current = Some((sdt, block.text.clone()));
+                    }
+                    (_, None) => flush(&mut current),
+                }
+                new_paragraph = false;
+            }
+        }
+        flush(&mut current);
+    }
+}
+
+/// The text of a paragraph, without formatting.
+pub(crate) fn plain_text(paragraph: &MarkdownParagraph) -> String {
+    paragraph
+        .blocks
+        .iter()
+        .filter(|block| block.text_type == TextType::Text)
+        .filter(|block| RevisionMode::Accept.shows(block.revision.as_ref()))
+        .map(|block| block.text.as_str())
+        .collect()
+}
+
+/// Serializes form fields as a map from their name to their type and value. When several
+/// fields have the same name, the later ones get a suffix that no other field uses, e.g.
+/// `name_2`.
+pub(crate) struct FormFieldMap<'a>(pub &'a [FormField]);
+
+impl Serialize for FormFieldMap<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct Entry<'a> {
+            #[serde(rename = "type")]
+            field_type: SdtType,
+            value: &'a Option<FieldValue>,
+        }
+
+        // Every field name is taken up front, so a suffix never clashes with a later field
+        let mut taken: HashSet<String> = self.0.iter().map(|f| f.name().to_string()).collect();
+        let mut seen: HashSet<&str> = HashSet::new();
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for field in self.0 {
+            let entry = Entry {
+                field_type: field.field_type,
+                value: &field.value,
+            };
+            if seen.insert(field.name()) {
+                map.serialize_entry(field.name(), &entry)?;
+            } else {
+                map.serialize_entry(&unique_key(field.name(), &mut taken), &entry)?;
+            }
+        }
+        map.end()
+    }
+}
@@ -0,0 +1,144 @@
+//! Sections and their page headers and footers.
+use docx_rust::document::{HeaderFooterReference, HeaderFooterReferenceType};
+use docx_rust::formatting::SectionProperty;
+use docx_rust::rels::Relationships;
+use hard_xml::XmlRead;
+use serde::Serialize;
+
+use crate::wordml::{self, BodyContent, Parts};
+use crate::{DocxParserError, MarkdownContent, MarkdownDocument};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize)]
+pub enum HeaderFooterKind {
+    /// Used on all pages, unless one of the others applies
+    Default,
+    /// Used on the first page of the section
+    First,
+    /// Used on even pages
+    Even,
+}
+
+impl From<&Option<HeaderFooterReferenceType>> for HeaderFooterKind {
+    fn from(ty: &Option<HeaderFooterReferenceType>) -> Self {
+        match ty {
+            Some(HeaderFooterReferenceType::First) => HeaderFooterKind::First,
+            Some(HeaderFooterReferenceType::Even) => HeaderFooterKind::Even,
+            _ => HeaderFooterKind::Default,
+        }
+    }
+}
+
+/// A page header or footer
+#[derive(Debug, Clone, Serialize)]
+pub struct HeaderFooter {
+    pub kind: HeaderFooterKind,
+    pub content: Vec<MarkdownContent>,
+}
+
+/// A section of the document. Sections that do not define a header or footer of some kind
+/// inherit it from the previous section, like Word does.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct Section {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<HeaderFooter>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub footers: Vec<HeaderFooter>,
+}
+
+impl Section {
+    /// Read the sections of the document body. A section ends with a paragraph that has section
+    /// properties, except for the last one, whose properties are at the end of the body.
+    pub(crate) fn read_all(
+        body: &[BodyContent],
+        parts: &Parts,
+        rels: Option<&Relationships>,
+        doc: &MarkdownDocument,
+    ) -> Result<Vec<Section>, DocxParserError> {
+        let mut sections: Vec<Section> = vec![];
+        for content in body {
+            let section_property = match content {
+                BodyContent::Paragraph(paragraph) => paragraph
+                    .property
+                    .as_ref()
+                    .and_then(|property| property.section_property.as_ref()),
+                BodyContent::SectionProperty(section_property) => Some(section_property),
+                _ => None,
+            };
+            if let Some(section_property) = section_property {
+                let mut section = Section::read(section_property, parts, rels, doc)?;
+                if let Some(previous) = sections.last() {
+                    section.inherit(previous);
+                }
+                sections.push(section);
+            }
+        }
+        Ok(sections)
+    }
+
+    fn read(
+        section_property: &SectionProperty,
+        parts: &Parts,
+        rels: Option<&Relationships>,
+        doc: &MarkdownDocument,
+    ) -> Result<Section, DocxParserError> {
+        let mut section = Section::default();
+        for reference in &section_property.header_footer_references {
+            let (ty, id) = match reference {
+                HeaderFooterReference::Header(header) => (&header.ty, &header.id),
+                HeaderFooterReference::Footer(footer) => (&footer.ty, &footer.id),
+            };
+            let target = id
+                .as_ref()
+                .and_then(|id| rels.and_then(|rels| rels.get_target(id)));
+            let Some(target) = target else {
+                continue;
+            };
+            // Targets are relative to `word/`, or to the root of the package when absolute
+            let name = match target.strip_prefix('/') {
+                Some(target) => target.to_string(),
+                None => format!("word/{target}"),
+            };
+            let Some(xml) = parts.get(&name) else {
+                continue;
+            };
+            let part_rels = parts.relationships(&name)?;
+            let kind = ty.into();
+            match reference {
+                HeaderFooterReference::Header(_) => {
+                    let header = wordml::Header::from_str(xml)?;
+                    let content =
+                        MarkdownContent::from_body(&header.content, part_rels.as_ref(), doc);
+                    section.headers.push(HeaderFooter { kind, content });
+                }
+                HeaderFooterReference::Footer(_) => {
+                    let footer = wordml::Footer::from_str(xml)?;
+                    let content =
+                        MarkdownContent::from_body(&footer.content, part_rels.as_ref(), doc);
+                    section.footers.push(HeaderFooter { kind, content });
+                }
+            }
+        }
+        Ok(section)
+    }
+
+    fn inherit(&mut self, previous: &Section) {
+        for header in &previous.headers {
+            if self.header(header.kind).is_none() {
+                self.headers.push(header.clone());
+            }
+        }
+        for footer in &previous.footers {
+            if self.footer(footer.kind).is_none() {
+                self.footers.push(footer.clone());
+            }
+        }
+    }
+
+    pub fn header(&self, kind: HeaderFooterKind) -> Option<&HeaderFooter> {
+        self.headers.iter().find(|header| header.kind == kind)
+    }
+
+    pub fn footer(&self, kind: HeaderFooterKind) -> Option<&HeaderFooter> {
+        self.footers.iter().find(|footer| footer.kind == kind)
+    }
+}
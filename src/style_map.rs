@@ -0,0 +1,319 @@
+//! A style map, like the one of mammoth, that converts paragraph and character styles to
+//! Markdown constructs.
+use crate::utils::{longest_run, quote};
+
+/// A Markdown construct for paragraphs of a style.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParagraphTarget {
+    /// A plain paragraph, also when the style is a heading style
+    Paragraph,
+    /// A heading of level 1 to 6, e.g. `h2`
+    Heading(usize),
+    /// A block quote, `blockquote`
+    BlockQuote,
+    /// A fenced code block, `pre`
+    CodeBlock,
+    /// A GitHub alert of the given kind, e.g. `admonition:warning` for `> [!WARNING]`
+    Admonition(String),
+    /// An item of a bulleted list, `ul`
+    BulletList,
+    /// An item of a numbered list, `ol`
+    OrderedList,
+    /// A pandoc fenced div with the custom style of the given name, e.g.
+    /// `::: {custom-style="Note Box"}`, which pandoc converts back to the style
+    CustomStyle(String),
+    /// Leave the paragraph out, `!`
+    Ignore,
+}
+
+/// A Markdown construct for runs of a character style.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunTarget {
+    /// Bold, `strong`
+    Bold,
+    /// Italics, `em`
+    Italics,
+    /// Strikethrough, `s`
+    Strike,
+    /// Inline code, `code`
+    Code,
+    /// Leave the text out, `!`
+    Ignore,
+}
+
+/// Selects a style by its name or id, ignoring case.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum StyleSelector {
+    Name(String),
+    Id(String),
+}
+
+impl StyleSelector {
+    fn matches(&self, id: Option<&str>, name: Option<&str>) -> bool {
+        let (selected, actual) = match self {
+            StyleSelector::Name(selected) => (selected, name),
+            StyleSelector::Id(selected) => (selected, id),
+        };
+        actual.is_some_and(|actual| actual.to_lowercase() == selected.to_lowercase())
+    }
+}
+
+/// Maps paragraph and character styles to Markdown constructs. The map is consulted before the
+/// built-in rules, and the first rule that matches a style applies.
+///
+/// A style map can be built in code:
+///
+/// ```
+/// use docx_parser::{ParagraphTarget, RunTarget, StyleMap};
+///
+/// let style_map = StyleMap::new()
+///     .paragraph("Note Box", ParagraphTarget::Admonition("note".to_string()))
+///     .paragraph("Code Sample", ParagraphTarget::CodeBlock)
+///     .run("Code Char", RunTarget::Code);
+/// ```
+///
+/// or parsed from the text format of mammoth, in which each line maps a paragraph (`p`) or run
+/// (`r`) style, by its name or id, to a construct, and lines that start with `#` are comments:
+///
+/// ```
+/// use docx_parser::StyleMap;
+///
+/// let style_map: StyleMap = "
+///     p[style-name='Note Box'] => admonition:note
+///     p[style-name='Code Sample'] => pre
+///     p.QuoteLarge => blockquote
+///     r[style-name='Code Char'] => code
+/// "
+/// .parse()
+/// .unwrap();
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct StyleMap {
+    paragraphs: Vec<(StyleSelector, ParagraphTarget)>,
+    runs: Vec<(StyleSelector, RunTarget)>,
+}
+
+impl StyleMap {
+    pub fn new() -> Self {
+        StyleMap::default()
+    }
+
+    /// Map the paragraph style with this name to a construct.
+    pub fn paragraph(mut self, style_name: &str, target: ParagraphTarget) -> Self {
+        self.paragraphs
+            .push((StyleSelector::Name(style_name.to_string()), target));
+        self
+    }
+
+    /// Map the character style with this name to a construct.
+    pub fn run(mut self, style_name: &str, target: RunTarget) -> Self {
+        self.runs
+            .push((StyleSelector::Name(style_name.to_string()), target));
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paragraphs.is_empty() && self.runs.is_empty()
+    }
+
+    /// The construct for a paragraph style, by its id and name.
+    pub fn paragraph_target(
+        &self,
+        id: Option<&str>,
+        name: Option<&str>,
+    ) -> Option<&ParagraphTarget> {
+        self.paragraphs
+            .iter()
+            .find(|(selector, _)| selector.matches(id, name))
+            .map(|(_, target)| target)
+    }
+
+    /// The construct for a character style, by its id and name.
+    pub fn run_target(&self, id: Option<&str>, name: Option<&str>) -> Option<RunTarget> {
+        self.runs
+            .iter()
+            .find(|(selector, _)| selector.matches(id, name))
+            .map(|(_, target)| *target)
+    }
+}
+
+impl std::str::FromStr for StyleMap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut style_map = StyleMap::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error =
+                |message: &str| format!("Invalid style map rule on line {}: {message}", i + 1);
+            let (selector, target) = line
+                .split_once("=>")
+                .ok_or_else(|| error("expected a rule like p[style-name='Name'] => blockquote"))?;
+            let (element, selector) = parse_selector(selector.trim()).ok_or_else(|| {
+                error("expected a selector like p[style-name='Name'], p.StyleId or r.StyleId")
+            })?;
+            let target = target.trim();
+            if element == 'p' {
+                let target = parse_paragraph_target(target)
+                    .ok_or_else(|| error(&format!("unsupported paragraph construct {target}")))?;
+                style_map.paragraphs.push((selector, target));
+            } else {
+                let target = parse_run_target(target)
+                    .ok_or_else(|| error(&format!("unsupported run construct {target}")))?;
+                style_map.runs.push((selector, target));
+            }
+        }
+        Ok(style_map)
+    }
+}
+
+/// Parse `p[style-name='Name']`, `p.StyleId` or the same for `r`.
+fn parse_selector(selector: &str) -> Option<(char, StyleSelector)> {
+    let element = selector.chars().next().filter(|c| matches!(c, 'p' | 'r'))?;
+    let rest = &selector[1..];
+    if let Some(id) = rest.strip_prefix('.') {
+        return (!id.is_empty()).then(|| (element, StyleSelector::Id(id.to_string())));
+    }
+    let name = rest.strip_prefix("[style-name=")?.strip_suffix(']')?.trim();
+    let name = name
+        .strip_prefix('\'')
+        .and_then(|name| name.strip_suffix('\''))
+        .or_else(|| {
+            name.strip_prefix('"')
+                .and_then(|name| name.strip_suffix('"'))
+        })?;
+    Some((element, StyleSelector::Name(name.to_string())))
+}
+
+fn parse_paragraph_target(target: &str) -> Option<ParagraphTarget> {
+    let target = target.to_lowercase();
+    Some(match target.as_str() {
+        "p" => ParagraphTarget::Paragraph,
+        "blockquote" => ParagraphTarget::BlockQuote,
+        "pre" | "code-block" => ParagraphTarget::CodeBlock,
+        "ul" => ParagraphTarget::BulletList,
+        "ol" => ParagraphTarget::OrderedList,
+        "!" => ParagraphTarget::Ignore,
+        _ => {
+            if let Some(kind) = target.strip_prefix("admonition:") {
+                ParagraphTarget::Admonition(kind.trim().to_string())
+            } else {
+                let level = target.strip_prefix('h')?.parse().ok()?;
+                (1..=6)
+                    .contains(&level)
+                    .then_some(ParagraphTarget::Heading(level))?
+            }
+        }
+    })
+}
+
+fn parse_run_target(target: &str) -> Option<RunTarget> {
+    Some(match target.to_lowercase().as_str() {
+        "strong" | "b" => RunTarget::Bold,
+        "em" | "i" => RunTarget::Italics,
+        "s" | "strike" | "del" => RunTarget::Strike,
+        "code" => RunTarget::Code,
+        "!" => RunTarget::Ignore,
+        _ => return None,
+    })
+}
+
+impl ParagraphTarget {
+    /// Wrap the Markdown of a paragraph in this construct.
+    pub(crate) fn wrap(&self, markdown: &str) -> String {
+        match self {
+            ParagraphTarget::Paragraph => markdown.to_string(),
+            ParagraphTarget::Heading(level) => format!("{} {markdown}", "#".repeat(*level)),
+            ParagraphTarget::BlockQuote => quote(markdown, 1),
+            ParagraphTarget::CodeBlock => {
+                // The fence is longer than any run of backticks in the code
+                let fence = "`".repeat(longest_run(markdown, '`').max(2) + 1);
+                format!("{fence}\n{markdown}\n{fence}")
+            }
+            ParagraphTarget::Admonition(kind) => {
+                format!("> [!{}]\n{}", kind.to_uppercase(), quote(markdown, 1))
+            }
+            ParagraphTarget::BulletList => format!("- {markdown}"),
+            ParagraphTarget::OrderedList => format!("1. {markdown}"),
+            ParagraphTarget::CustomStyle(name) => format!(
+                "::: {{custom-style=\"{}\"}}\n{markdown}\n:::",
+                name.replace('"', "\\\"")
+            ),
+            ParagraphTarget::Ignore => String::new(),
+        }
+    }
+
+    /// Join the Markdown of a paragraph with this construct to that of the previous paragraph
+    /// with the same construct, so that they form a single code block, quote or list.
+    pub(crate) fn join(&self, previous: &str, next: &str) -> Option<String> {
+        let previous = previous.trim_end_matches('\n');
+        match self {
+            ParagraphTarget::BlockQuote => Some(format!("{previous}\n>\n{next}")),
+            ParagraphTarget::CodeBlock => Some(self.wrap(&format!(
+                "{}\n{}",
+                fenced_code(previous)?,
+                fenced_code(next)?
+            ))),
+            ParagraphTarget::Admonition(_) => {
+                Some(format!("{previous}\n>\n{}", next.split_once('\n')?.1))
+            }
+            ParagraphTarget::CustomStyle(_) => Some(format!(
+                "{}\n\n{}",
+                previous.strip_suffix("\n:::")?,
+                next.split_once('\n')?.1
+            )),
+            ParagraphTarget::BulletList | ParagraphTarget::OrderedList => {
+                Some(format!("{previous}\n{next}"))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The code in a fenced code block.
+fn fenced_code(markdown: &str) -> Option<&str> {
+    let (_fence, code) = markdown.split_once('\n')?;
+    Some(code.rsplit_once('\n')?.0)
+}
+
+#[test]
+fn test_parse_style_map() {
+    let style_map: StyleMap = "
+        # House styles
+        p[style-name='Note Box'] => admonition:note
+        p[style-name=\"Code Sample\"] => pre
+        p.QuoteLarge => blockquote
+        r[style-name='Code Char'] => code
+    "
+    .parse()
+    .unwrap();
+    assert_eq!(
+        Some(&ParagraphTarget::Admonition("note".to_string())),
+        style_map.paragraph_target(Some("NoteBox"), Some("note box"))
+    );
+    assert_eq!(
+        Some(&ParagraphTarget::CodeBlock),
+        style_map.paragraph_target(None, Some("Code Sample"))
+    );
+    assert_eq!(
+        Some(&ParagraphTarget::BlockQuote),
+        style_map.paragraph_target(Some("QuoteLarge"), Some("Quote Large"))
+    );
+    assert_eq!(
+        None,
+        style_map.paragraph_target(Some("Quote"), Some("Quote"))
+    );
+    assert_eq!(
+        Some(RunTarget::Code),
+        style_map.run_target(Some("CodeChar"), Some("Code Char"))
+    );
+
+    assert!("p[style-name='Note Box'] => h7"
+        .parse::<StyleMap>()
+        .is_err());
+    assert!("x.Note => blockquote".parse::<StyleMap>().is_err());
+    assert!("p.Note blockquote".parse::<StyleMap>().is_err());
+}
@@ -0,0 +1,328 @@
+//! Paragraph and character styles, resolved along the styles they are based on.
+use docx_rust::styles::{Style, StyleType, Styles};
+use std::collections::HashMap;
+
+use crate::{BlockStyle, MarkdownContent, MarkdownDocument, MarkdownParagraph, ParagraphStyle};
+
+/// Read the paragraph and character styles of the document, keyed by their id. Each style
+/// includes the formatting of the styles it is based on, and paragraph styles also that of the
+/// document defaults and of their linked character style. Also sets the id of the default
+/// paragraph style, which applies to paragraphs without a style.
+pub(crate) fn read_styles(styles: &Styles, markdown_doc: &mut MarkdownDocument) {
+    let character_styles = of_type(styles, |ty| matches!(ty, StyleType::Character));
+    for (id, style) in &character_styles {
+        let mut effective = BlockStyle::new();
+        for style in based_on_chain(style, &character_styles) {
+            if let Some(character_property) = &style.character {
+                effective.combine_with(&character_property.into());
+            }
+        }
+        if let Some(name) = &style.name {
+            markdown_doc
+                .character_style_names
+                .insert(id.to_string(), name.value.to_string());
+        }
+        effective.code |= has_name(style, &CODE_STYLES);
+        effective.character_style = Some(id.to_string());
+        markdown_doc
+            .character_styles
+            .insert(id.to_string(), effective);
+    }
+
+    let paragraph_styles = of_type(styles, |ty| matches!(ty, StyleType::Paragraph));
+    let mut defaults: ParagraphStyle = match styles
+        .default
+        .as_ref()
+        .and_then(|default| default.paragraph.inner.as_ref())
+    {
+        Some(paragraph_property) => paragraph_property.into(),
+        None => ParagraphStyle::new(),
+    };
+    if let Some(character_property) = styles
+        .default
+        .as_ref()
+        .and_then(|default| default.character.inner.as_ref())
+    {
+        defaults.combine_with(&ParagraphStyle {
+            style: Some(run_formatting(character_property.into())),
+            ..ParagraphStyle::new()
+        });
+    }
+    for (id, style) in &paragraph_styles {
+        let mut effective = defaults.clone();
+        for style in based_on_chain(style, &paragraph_styles).iter().rev() {
+            let mut paragraph_style: ParagraphStyle = match &style.paragraph {
+                Some(paragraph_property) => paragraph_property.into(),
+                None => ParagraphStyle::new(),
+            };
+            paragraph_style.style_id = Some(style.style_id.to_string());
+            paragraph_style.name = style.name.as_ref().map(|name| name.value.to_string());
+            paragraph_style.quote = has_name(style, &QUOTE_STYLES);
+            paragraph_style.code_block = has_name(style, &CODE_BLOCK_STYLES)
+                || style
+                    .character
+                    .as_ref()
+                    .is_some_and(|character_property| BlockStyle::from(character_property).code);
+            if paragraph_style.outline_lvl.is_none() {
+                paragraph_style.outline_lvl = builtin_heading_level(style).map(|level| level - 1);
+            }
+            // The run formatting of the style itself, completed by that of its linked character
+            // style, which Word keeps in sync with it
+            let mut formatting = style
+                .character
+                .as_ref()
+                .map(BlockStyle::from)
+                .unwrap_or_default();
+            let linked = style
+                .link
+                .as_ref()
+                .and_then(|link| markdown_doc.character_styles.get(link.value.as_ref()));
+            if let Some(linked) = linked {
+                formatting.combine_with(linked);
+            }
+            let formatting = run_formatting(formatting);
+            match &mut paragraph_style.style {
+                Some(style) => style.combine_with(&formatting),
+                None => paragraph_style.style = Some(formatting),
+            }
+            paragraph_style.combine_with(&effective);
+            effective = paragraph_style;
+        }
+        markdown_doc.styles.insert(id.to_string(), effective);
+    }
+    markdown_doc.default_style = paragraph_styles
+        .values()
+        .find(|style| style.default == Some(true))
+        .map(|style| style.style_id.to_string());
+
+    markdown_doc.custom_styles = styles
+        .styles
+        .iter()
+        .filter(|style| style.custom_style == Some(true))
+        .map(|style| style.style_id.to_string())
+        .collect();
+}
+
+/// The run formatting of a paragraph style or the document defaults, which applies to the runs
+/// of the paragraph. Whether these are code follows from the paragraph style instead, and the
+/// character style is that of the runs themselves.
+fn run_formatting(formatting: BlockStyle) -> BlockStyle {
+    BlockStyle {
+        code: false,
+        character_style: None,
+        ..formatting
+    }
+}
+
+/// The names of the built-in heading styles in English and some other languages, without spaces.
+/// Word names them `heading 1` to `heading 9` in any language, but other applications use the
+/// localized name, and Word uses it for the id of the style, without the characters outside ASCII,
+/// e.g. `berschrift1`.
+const HEADING_NAMES: [&str; 21] = [
+    "heading",
+    "kop",
+    "überschrift",
+    "berschrift",
+    "titre",
+    "título",
+    "ttulo",
+    "titulo",
+    "titolo",
+    "rubrik",
+    "overskrift",
+    "otsikko",
+    "nagłówek",
+    "nagwek",
+    "címsor",
+    "cmsor",
+    "nadpis",
+    "başlık",
+    "balk",
+    "заголовок",
+    "見出し",
+];
+
+/// The names of the built-in title style in English and some other languages.
+const TITLE_NAMES: [&str; 8] = [
+    "title", "titel", "titre", "título", "ttulo", "titulo", "titolo", "tytuł",
+];
+
+/// Whether a style id or name is that of the built-in `Title` style, also when localized.
+pub(crate) fn is_title_style(name: &str) -> bool {
+    TITLE_NAMES.contains(&normalize(name).as_str())
+}
+
+/// The heading level of a built-in heading style, from its id or name, where `Title` is a
+/// heading of level 1 as well.
+fn builtin_heading_level(style: &Style) -> Option<isize> {
+    std::iter::once(style.style_id.as_ref())
+        .chain(style.name.as_ref().map(|name| name.value.as_ref()))
+        .find_map(|name| {
+            let name = normalize(name);
+            let prefix = name.trim_end_matches(|c: char| c.is_ascii_digit());
+            match &name[prefix.len()..] {
+                "" if TITLE_NAMES.contains(&prefix) => Some(1),
+                level if HEADING_NAMES.contains(&prefix) => {
+                    level.parse().ok().filter(|level| (1..=9).contains(level))
+                }
+                _ => None,
+            }
+        })
+}
+
+/// Fonts in which all characters have the same width, besides the ones with `Mono` or `Code` in
+/// their name, without spaces.
+const MONOSPACE_FONTS: [&str; 14] = [
+    "consolas",
+    "courier",
+    "couriernew",
+    "menlo",
+    "monaco",
+    "lucidaconsole",
+    "lucidasanstypewriter",
+    "inconsolata",
+    "hack",
+    "ocra",
+    "monospace",
+    "sourcecodepro",
+    "firacode",
+    "cascadiacode",
+];
+
+/// The names of paragraph styles for code blocks, without spaces.
+const CODE_BLOCK_STYLES: [&str; 8] = [
+    "sourcecode",
+    "htmlpreformatted",
+    "preformattedtext",
+    "code",
+    "codeblock",
+    "codesample",
+    "plaintext",
+    "macrotext",
+];
+
+/// The names of character styles for inline code, without spaces.
+const CODE_STYLES: [&str; 9] = [
+    "verbatimchar",
+    "htmlcode",
+    "htmlkeyboard",
+    "htmlsample",
+    "htmltypewriter",
+    "code",
+    "codechar",
+    "inlinecode",
+    "sourcecode",
+];
+
+/// The names of paragraph styles for block quotes, without spaces.
+const QUOTE_STYLES: [&str; 7] = [
+    "quote",
+    "intensequote",
+    "blocktext",
+    "zitat",
+    "citaat",
+    "citation",
+    "cita",
+];
+
+/// The name in lowercase, without spaces.
+fn normalize(name: &str) -> String {
+    name.to_lowercase()
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect()
+}
+
+/// Whether text in the font is code, like in `Consolas`, `Courier New` or a `… Mono` family
+/// such as `Roboto Mono`, but not `Monotype Corsiva` or a barcode font like `Code 128`.
+pub(crate) fn is_monospace_font(font: &str) -> bool {
+    let is_mono = |word: &str| word.eq_ignore_ascii_case("mono");
+    let normalized = normalize(font);
+    font.split(|c: char| c.is_whitespace() || c == '-')
+        .any(is_mono)
+        || normalized.ends_with("mono")
+        || MONOSPACE_FONTS.contains(&normalized.as_str())
+}
+
+/// Whether the id or name of the style is one of `names`.
+fn has_name(style: &Style, names: &[&str]) -> bool {
+    std::iter::once(style.style_id.as_ref())
+        .chain(style.name.as_ref().map(|name| name.value.as_ref()))
+        .any(|name| names.contains(&normalize(name).as_str()))
+}
+
+/// The styles of one type, keyed by their id.
+fn of_type<'a, 'b>(
+    styles: &'b Styles<'a>,
+    is_type: fn(&StyleType) -> bool,
+) -> HashMap<&'b str, &'b Style<'a>> {
+    styles
+        .styles
+        .iter()
+        .filter(|style| style.ty.as_ref().is_some_and(is_type))
+        .map(|style| (style.style_id.as_ref(), style))
+        .collect()
+}
+
+/// The chain of styles from `style` to the one it is ultimately based on.
+fn based_on_chain<'a, 'b>(
+    style: &'b Style<'a>,
+    styles: &HashMap<&str, &'b Style<'a>>,
+) -> Vec<&'b Style<'a>> {
+    let mut chain = vec![style];
+    let mut current = style;
+    while let Some(base) = &current.base {
+        match styles.get(base.value.as_ref()) {
+            Some(base) if !chain.iter().any(|style| style.style_id == base.style_id) => {
+                chain.push(base);
+                current = base;
+            }
+            _ => break,
+        }
+    }
+    chain
+}
+
+/// Add the formatting of the character style of each run to its own formatting, in the body,
+/// headers, footers, notes and comments.
+pub(crate) fn apply_character_styles(markdown_doc: &mut MarkdownDocument) {
+    let character_styles = std::mem::take(&mut markdown_doc.character_styles);
+    let apply = |paragraph: &mut MarkdownParagraph| {
+        for style in paragraph
+            .blocks
+            .iter_mut()
+            .filter_map(|block| block.style.as_mut())
+        {
+            let character_style = style
+                .character_style
+                .as_ref()
+                .and_then(|id| character_styles.get(id));
+            if let Some(character_style) = character_style {
+                style.combine_with(character_style);
+            }
+        }
+    };
+
+    markdown_doc.paragraphs_mut().for_each(apply);
+    markdown_doc
+        .sections
+        .iter_mut()
+        .flat_map(|section| section.headers.iter_mut().chain(section.footers.iter_mut()))
+        .flat_map(|header_footer| header_footer.content.iter_mut())
+        .flat_map(MarkdownContent::paragraphs_mut)
+        .for_each(apply);
+    markdown_doc
+        .notes
+        .iter_mut()
+        .flat_map(|note| note.content.iter_mut())
+        .for_each(apply);
+    for comment in markdown_doc.comments.iter_mut() {
+        comment.content.iter_mut().for_each(apply);
+        comment
+            .replies
+            .iter_mut()
+            .flat_map(|reply| reply.content.iter_mut())
+            .for_each(apply);
+    }
+    markdown_doc.character_styles = character_styles;
+}
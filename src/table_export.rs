@@ -0,0 +1,155 @@
+//! Export of the data in tables, as CSV, TSV or JSON records. Cells that span several columns or
+//! rows are expanded, i.e. their text is repeated in each column and row that they span.
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Serialize, Serializer};
+use std::collections::HashSet;
+
+use crate::sdt::plain_text;
+use crate::tables::place_cells;
+use crate::utils::unique_key;
+use crate::{
+    DocxParserError, MarkdownContent, MarkdownTable, MarkdownTableRow, TableExportFormat,
+    TableExportOptions,
+};
+
+/// Convert the data in a table to the export format.
+pub(crate) fn export_table(
+    table: &MarkdownTable,
+    options: &TableExportOptions,
+) -> Result<String, DocxParserError> {
+    let rows = expand_cells(table);
+    let export = match options.format {
+        TableExportFormat::Csv => to_separated_values(&rows, ',', "\r\n", csv_field),
+        TableExportFormat::Tsv => to_separated_values(&rows, '\t', "\n", tsv_field),
+        TableExportFormat::Json => {
+            let header_rows = match table.iter().take_while(|row| row.is_header).count() {
+                0 if options.first_row_as_header => 1.min(rows.len()),
+                count => count,
+            };
+            let (header, body) = rows.split_at(header_rows);
+            let records = Records {
+                keys: record_keys(header, rows.first().map(Vec::len).unwrap_or_default()),
+                rows: body,
+            };
+            serde_json::to_string_pretty(&records)?
+        }
+    };
+    Ok(export)
+}
+
+/// The text of each grid column of each row of the table.
+fn expand_cells(table: &MarkdownTable) -> Vec<Vec<String>> {
+    let (placed, columns) = place_cells(table);
+    let mut rows = vec![vec![String::new(); columns]; table.len()];
+    for (row, (MarkdownTableRow { cells, .. }, placed)) in table.iter().zip(&placed).enumerate() {
+        for (cell, &column) in cells.iter().zip(placed) {
+            let text = cell_text(&cell.content);
+            for expanded in &mut rows[row..(row + cell.rowspan).min(table.len())] {
+                expanded[column..column + cell.colspan].fill(text.clone());
+            }
+        }
+    }
+    rows
+}
+
+/// The text of the paragraphs of a cell, including those in nested tables, one per line.
+fn cell_text(content: &[MarkdownContent]) -> String {
+    content
+        .iter()
+        .flat_map(MarkdownContent::paragraphs)
+        .map(plain_text)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+fn to_separated_values(
+    rows: &[Vec<String>],
+    separator: char,
+    line_end: &str,
+    field: fn(&str) -> String,
+) -> String {
+    rows.iter()
+        .map(|row| {
+            let fields: Vec<String> = row.iter().map(|text| field(text)).collect();
+            format!("{}{line_end}", fields.join(&separator.to_string()))
+        })
+        .collect()
+}
+
+/// A CSV field, quoted when it holds a comma, quote or line break. Together with the CRLF at the
+/// end of each row, this follows RFC 4180.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// A TSV field, which cannot hold tabs or line breaks.
+fn tsv_field(text: &str) -> String {
+    text.replace(['\t', '\n', '\r'], " ")
+}
+
+/// The keys of the records: the text of the header rows in each column, skipping the text that
+/// a cell repeats in the rows that it spans, or `column_1`, `column_2`, etc. for columns without
+/// a header. When several columns have the same key, the later ones get a suffix that no other
+/// column uses, e.g. `name_2`.
+fn record_keys(header: &[Vec<String>], columns: usize) -> Vec<String> {
+    let keys: Vec<String> = (0..columns)
+        .map(|column| {
+            let mut texts: Vec<&str> = header
+                .iter()
+                .map(|row| row[column].as_str())
+                .filter(|text| !text.is_empty())
+                .collect();
+            texts.dedup();
+            match texts.join(" ") {
+                key if key.is_empty() => format!("column_{}", column + 1),
+                key => key.replace('\n', " "),
+            }
+        })
+        .collect();
+    // Every key is taken up front, so a suffix never clashes with a later column
+    let mut taken: HashSet<String> = keys.iter().cloned().collect();
+    let mut seen: HashSet<&str> = HashSet::new();
+    keys.iter()
+        .map(|key| {
+            if seen.insert(key) {
+                key.clone()
+            } else {
+                unique_key(key, &mut taken)
+            }
+        })
+        .collect()
+}
+
+/// Serializes the rows of a table as an array of objects, with the keys in column order.
+struct Records<'a> {
+    keys: Vec<String>,
+    rows: &'a [Vec<String>],
+}
+
+impl Serialize for Records<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Record<'a>(&'a [String], &'a [String]);
+
+        impl Serialize for Record<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.0.len()))?;
+                for (key, value) in self.0.iter().zip(self.1) {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+
+        let mut seq = serializer.serialize_seq(Some(self.rows.len()))?;
+        for row in self.rows {
+            seq.serialize_element(&Record(&self.keys, row))?;
+        }
+        seq.end()
+    }
+}
@@ -0,0 +1,801 @@
+//! Tables, with cells that span several columns or rows and that can hold nested tables. Tables
+//! are rendered as pipe, grid, simple, multiline or HTML tables, and tables that a format cannot
+//! represent, like ones with merged cells, as HTML or grid tables.
+use docx_rust::formatting::OnOffOnlyType;
+use docx_rust::rels::Relationships;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::utils::{display_width, max_lengths_per_column, table_row_to_markdown};
+use crate::{
+    wordml, MarkdownContent, MarkdownDocument, MarkdownOptions, MarkdownParagraph,
+    MergedTableStyle, TableFormat,
+};
+
+pub type MarkdownTable = Vec<MarkdownTableRow>;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownTableRow {
+    pub(crate) is_header: bool,
+    pub(crate) cells: Vec<MarkdownTableCell>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MarkdownTableCell {
+    /// The paragraphs, nested tables and content controls of the cell
+    pub content: Vec<MarkdownContent>,
+    /// The number of columns that the cell spans
+    #[serde(skip_serializing_if = "is_one")]
+    pub colspan: usize,
+    /// The number of rows that the cell spans
+    #[serde(skip_serializing_if = "is_one")]
+    pub rowspan: usize,
+    /// The width of the grid columns that the cell spans, in twentieths of a point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<usize>,
+    /// The alignment of the first paragraph of the cell
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alignment: Option<Alignment>,
+}
+
+/// The horizontal alignment of a paragraph, or of a column of a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum Alignment {
+    Left,
+    Center,
+    Right,
+}
+
+fn is_one(span: &usize) -> bool {
+    *span == 1
+}
+
+/// Read a table. Cells that continue a vertically merged cell are left out, and add to the
+/// rowspan of the cell that they continue. Each cell gets the width of its grid columns and the
+/// alignment of its first paragraph.
+pub(crate) fn read_table(
+    table: &wordml::Table,
+    rels: Option<&Relationships>,
+    doc: &MarkdownDocument,
+) -> MarkdownTable {
+    let mut rows: MarkdownTable = vec![];
+    // The row and cell index of the cell that starts in each grid column
+    let mut origins: HashMap<usize, (usize, usize)> = HashMap::new();
+    let grid_widths: Vec<usize> = table
+        .grids
+        .columns
+        .iter()
+        .map(|column| column.width.max(0) as usize)
+        .collect();
+    for row in table.rows() {
+        // A header row has `<w:tblHeader/>`, where the value defaults to on
+        let is_header = match &row.property.table_header {
+            Some(table_header) => !matches!(table_header.value, Some(OnOffOnlyType::Off)),
+            None => false,
+        };
+        let mut cells = vec![];
+        let mut column = 0;
+        for cell in row.cells() {
+            let colspan = cell
+                .property
+                .grid_span
+                .as_ref()
+                .and_then(|grid_span| grid_span.value)
+                .unwrap_or(1)
+                .max(1);
+            let v_merge = cell
+                .property
+                .v_merge
+                .as_ref()
+                .map(|v_merge| v_merge.value.as_deref().unwrap_or("continue"));
+            let origin = origins.get(&column).copied();
+            match (v_merge, origin) {
+                (Some(v_merge), Some((row, cell))) if v_merge != "restart" => {
+                    rows[row].cells[cell].rowspan += 1;
+                }
+                _ => {
+                    origins.insert(column, (rows.len(), cells.len()));
+                    let content = MarkdownContent::from_body(&cell.content, rels, doc);
+                    let alignment = content
+                        .iter()
+                        .flat_map(MarkdownContent::paragraphs)
+                        .next()
+                        .and_then(|paragraph| paragraph.effective_style(doc).alignment);
+                    let width = grid_widths
+                        .get(column..column + colspan)
+                        .filter(|widths| widths.iter().all(|width| *width > 0))
+                        .map(|widths| widths.iter().sum());
+                    cells.push(MarkdownTableCell {
+                        content,
+                        colspan,
+                        rowspan: 1,
+                        width,
+                        alignment,
+                    });
+                }
+            }
+            column += colspan;
+        }
+        rows.push(MarkdownTableRow { is_header, cells });
+    }
+    rows
+}
+
+/// Whether no cell of the table spans several columns or rows, or holds a nested table.
+fn has_plain_cells(table: &MarkdownTable) -> bool {
+    fn has_table(content: &MarkdownContent) -> bool {
+        match content {
+            MarkdownContent::Paragraph(_) => false,
+            MarkdownContent::Table(_) => true,
+            MarkdownContent::Sdt(sdt) => sdt.content.iter().any(has_table),
+        }
+    }
+    table
+        .iter()
+        .flat_map(|row| row.cells.iter())
+        .all(|cell| cell.colspan == 1 && cell.rowspan == 1 && !cell.content.iter().any(has_table))
+}
+
+/// How the content of a cell is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CellFormat {
+    /// Each paragraph on a single line, for a pipe, simple or multiline table
+    Inline,
+    /// Each paragraph on a single line, and nested tables in HTML, for an HTML table
+    Html,
+    /// Markdown blocks, for a grid table
+    Blocks,
+}
+
+/// A row, with whether it is a header row, and its cells with the Markdown of their content.
+type RenderedRow<'a> = (bool, Vec<(&'a MarkdownTableCell, Vec<String>)>);
+
+fn render_rows<'a>(
+    table: &'a MarkdownTable,
+    cell_to_markdown: &mut impl FnMut(&MarkdownTableCell, CellFormat) -> Vec<String>,
+    format: CellFormat,
+) -> Vec<RenderedRow<'a>> {
+    table
+        .iter()
+        .map(|row| {
+            let cells = row
+                .cells
+                .iter()
+                .map(|cell| (cell, cell_to_markdown(cell, format)))
+                .collect();
+            (row.is_header, cells)
+        })
+        .collect()
+}
+
+/// Whether each cell of the table holds at most one paragraph, so that it fits on a line.
+fn has_single_paragraph_cells(table: &MarkdownTable) -> bool {
+    table.iter().flat_map(|row| row.cells.iter()).all(|cell| {
+        cell.content
+            .iter()
+            .flat_map(MarkdownContent::paragraphs)
+            .count()
+            <= 1
+    })
+}
+
+/// Whether a pipe table can represent the table: it has plain cells of a single paragraph, and
+/// starts with a header row, which a pipe table cannot do without.
+fn is_pipe_table(table: &MarkdownTable) -> bool {
+    has_plain_cells(table)
+        && has_single_paragraph_cells(table)
+        && table.first().is_some_and(|row| row.is_header)
+}
+
+/// Whether the table has plain cells of a single paragraph, and each row has some text, so that
+/// each row fits on a line of a simple table, or a block of lines of a multiline table.
+fn has_single_line_rows(table: &MarkdownTable) -> bool {
+    has_plain_cells(table)
+        && has_single_paragraph_cells(table)
+        && table.iter().all(|row| {
+            row.cells
+                .iter()
+                .flat_map(|cell| cell.content.iter().flat_map(MarkdownContent::paragraphs))
+                .any(MarkdownParagraph::has_text)
+        })
+}
+
+/// The grid column in which each cell of the table starts, next to the cells that span rows
+/// from above, and the number of grid columns.
+pub(crate) fn place_cells(table: &MarkdownTable) -> (Vec<Vec<usize>>, usize) {
+    let mut occupied: Vec<Vec<bool>> = vec![];
+    let mut placed = vec![];
+    for (row, MarkdownTableRow { cells, .. }) in table.iter().enumerate() {
+        let mut columns = vec![];
+        let mut column = 0;
+        for cell in cells {
+            while occupied
+                .get(row)
+                .is_some_and(|columns| columns.get(column) == Some(&true))
+            {
+                column += 1;
+            }
+            for r in row..row + cell.rowspan.min(table.len() - row) {
+                if occupied.len() <= r {
+                    occupied.resize(r + 1, vec![]);
+                }
+                if occupied[r].len() < column + cell.colspan {
+                    occupied[r].resize(column + cell.colspan, false);
+                }
+                occupied[r][column..column + cell.colspan].fill(true);
+            }
+            columns.push(column);
+            column += cell.colspan;
+        }
+        placed.push(columns);
+    }
+    let columns = occupied.iter().map(Vec::len).max().unwrap_or_default();
+    (placed, columns)
+}
+
+/// The alignment and the width in Word of each grid column of a table.
+struct Columns {
+    /// The alignment that the cells with text in a column share, leaving out the header rows
+    /// when the table has other rows
+    alignments: Vec<Option<Alignment>>,
+    /// The widths in twentieths of a point, when all columns have one
+    widths: Option<Vec<usize>>,
+}
+
+impl Columns {
+    fn of(table: &MarkdownTable) -> Self {
+        let (placed, columns) = place_cells(table);
+        let has_body = table.iter().any(|row| !row.is_header);
+        let mut alignments: Vec<Option<Option<Alignment>>> = vec![None; columns];
+        let mut widths: Vec<Option<usize>> = vec![None; columns];
+        for (row, columns) in table.iter().zip(&placed) {
+            for (cell, column) in row.cells.iter().zip(columns) {
+                if cell.colspan > 1 {
+                    continue;
+                }
+                widths[*column] = widths[*column].or(cell.width);
+                let has_text = cell
+                    .content
+                    .iter()
+                    .flat_map(MarkdownContent::paragraphs)
+                    .any(MarkdownParagraph::has_text);
+                if has_text && (!row.is_header || !has_body) {
+                    // A column without a shared alignment has none
+                    let alignment = &mut alignments[*column];
+                    *alignment = match alignment {
+                        Some(shared) if *shared != cell.alignment => Some(None),
+                        _ => Some(alignment.unwrap_or(cell.alignment)),
+                    };
+                }
+            }
+        }
+        Columns {
+            alignments: alignments.into_iter().map(Option::flatten).collect(),
+            widths: widths.into_iter().collect(),
+        }
+    }
+
+    fn alignment(&self, column: usize) -> Option<Alignment> {
+        self.alignments.get(column).copied().flatten()
+    }
+
+    /// Widen the columns, of at least `widths` characters, so that they are in proportion to
+    /// their widths in Word where possible.
+    fn proportional(&self, widths: &[usize]) -> Vec<usize> {
+        let Some(word_widths) = self.widths.as_ref().filter(|word_widths| {
+            word_widths.len() == widths.len() && word_widths.iter().all(|width| *width > 0)
+        }) else {
+            return widths.to_vec();
+        };
+        let total: usize = widths.iter().sum();
+        let word_total: usize = word_widths.iter().sum();
+        widths
+            .iter()
+            .zip(word_widths)
+            .map(|(width, word_width)| {
+                (*width).max((word_width * total + word_total / 2) / word_total)
+            })
+            .collect()
+    }
+}
+
+/// The text padded with spaces to the width, on the side or sides given by the alignment.
+fn align(text: &str, width: usize, alignment: Option<Alignment>) -> String {
+    let padding = width.saturating_sub(display_width(text));
+    let left = match alignment {
+        Some(Alignment::Right) => padding,
+        Some(Alignment::Center) => padding / 2,
+        _ => 0,
+    };
+    format!("{}{text}{}", " ".repeat(left), " ".repeat(padding - left))
+}
+
+/// The rows of the table with the paragraph of each cell on a single line.
+fn inline_rows(
+    table: &MarkdownTable,
+    cell_to_markdown: &mut impl FnMut(&MarkdownTableCell, CellFormat) -> Vec<String>,
+) -> Vec<(bool, Vec<String>)> {
+    render_rows(table, cell_to_markdown, CellFormat::Inline)
+        .into_iter()
+        .map(|(is_header, cells)| {
+            let cells = cells
+                .into_iter()
+                .map(|(_, paragraphs)| paragraphs.concat())
+                .collect();
+            (is_header, cells)
+        })
+        .collect()
+}
+
+/// Render a table in the table format of the options, where `cell_to_markdown` renders the
+/// content of a cell in a format. Tables that the table format cannot represent are rendered in
+/// the `merged_tables` style.
+pub(crate) fn table_to_markdown(
+    table: &MarkdownTable,
+    mut cell_to_markdown: impl FnMut(&MarkdownTableCell, CellFormat) -> Vec<String>,
+    options: &MarkdownOptions,
+) -> String {
+    let fallback = match options.merged_tables {
+        MergedTableStyle::Html => TableFormat::Html,
+        MergedTableStyle::Grid => TableFormat::Grid,
+    };
+    let format = match options.table_format {
+        TableFormat::Pipe if !is_pipe_table(table) => fallback,
+        TableFormat::Simple | TableFormat::Multiline if !has_single_line_rows(table) => fallback,
+        format => format,
+    };
+    let columns = Columns::of(table);
+    match format {
+        TableFormat::Pipe => pipe_table(&inline_rows(table, &mut cell_to_markdown), &columns),
+        TableFormat::Simple => simple_table(&inline_rows(table, &mut cell_to_markdown), &columns),
+        TableFormat::Multiline => {
+            multiline_table(&inline_rows(table, &mut cell_to_markdown), &columns)
+        }
+        TableFormat::Html => table_to_html(table, cell_to_markdown),
+        TableFormat::Grid => grid_table(
+            table,
+            &render_rows(table, &mut cell_to_markdown, CellFormat::Blocks),
+            &columns,
+        ),
+        TableFormat::Skip => String::new(),
+    }
+}
+
+/// A pipe table, with the alignment of centered and right aligned columns in the divider, e.g.
+/// `| :---: | ---: |`. Like in pandoc, left aligned columns, the default in Word, get no marker.
+fn pipe_table(rows: &[(bool, Vec<String>)], columns: &Columns) -> String {
+    let column_lengths = max_lengths_per_column(rows, 3);
+    let divider = &table_row_to_markdown(
+        &column_lengths,
+        &column_lengths
+            .iter()
+            .enumerate()
+            .map(|(i, width)| match columns.alignment(i) {
+                Some(Alignment::Center) => format!(":{}:", "-".repeat(width - 2)),
+                Some(Alignment::Right) => format!("{}:", "-".repeat(width - 1)),
+                _ => "-".repeat(*width),
+            })
+            .collect::<Vec<_>>(),
+    );
+    rows.iter()
+        .enumerate()
+        .fold("".to_string(), |mut acc, (i, (_, row))| {
+            acc.push_str(&table_row_to_markdown(&column_lengths, row));
+            if i == 0 {
+                acc.push_str(divider);
+            }
+            acc
+        })
+}
+
+/// The width of each column: that of its widest line, and at least 3.
+fn column_widths(rows: &[(bool, Vec<Vec<String>>)]) -> Vec<usize> {
+    let mut widths = vec![];
+    for (_, cells) in rows {
+        if widths.len() < cells.len() {
+            widths.resize(cells.len(), 3);
+        }
+        for (width, lines) in widths.iter_mut().zip(cells) {
+            for line in lines {
+                *width = (*width).max(display_width(line));
+            }
+        }
+    }
+    widths
+}
+
+/// The lines of a row, with the lines of each cell padded to the width of its column as it is
+/// aligned.
+fn row_lines(widths: &[usize], cells: &[Vec<String>], columns: &Columns) -> String {
+    let height = cells.iter().map(Vec::len).max().unwrap_or_default();
+    (0..height)
+        .map(|i| {
+            let line = widths
+                .iter()
+                .enumerate()
+                .map(|(j, width)| {
+                    let text = cells.get(j).and_then(|lines| lines.get(i));
+                    let text = text.map(String::as_str).unwrap_or_default();
+                    align(text, *width, columns.alignment(j))
+                })
+                .collect::<Vec<_>>()
+                .join("  ");
+            line.trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+/// Widen the columns so that the first row, which determines the alignment of the columns of a
+/// simple or multiline table, does not fill the width of centered and right aligned columns.
+fn widen_for_alignment(widths: &mut [usize], first_row: &[Vec<String>], columns: &Columns) {
+    for (i, (width, lines)) in widths.iter_mut().zip(first_row).enumerate() {
+        let margin = match columns.alignment(i) {
+            Some(Alignment::Center) => 2,
+            Some(Alignment::Right) => 1,
+            _ => 0,
+        };
+        let text_width = lines.iter().map(|line| display_width(line)).max();
+        *width = (*width).max(text_width.unwrap_or_default() + margin);
+    }
+}
+
+/// A line of dashes under each column.
+fn column_dashes(widths: &[usize]) -> String {
+    widths
+        .iter()
+        .map(|width| "-".repeat(*width))
+        .collect::<Vec<_>>()
+        .join("  ")
+        + "\n"
+}
+
+/// A pandoc simple table, with the first row as its header when it is a header row, or else
+/// between two lines of dashes. The columns are aligned by the position of their text.
+fn simple_table(rows: &[(bool, Vec<String>)], columns: &Columns) -> String {
+    let rows: Vec<(bool, Vec<Vec<String>>)> = rows
+        .iter()
+        .map(|(is_header, cells)| {
+            let cells = cells.iter().map(|cell| vec![cell.clone()]).collect();
+            (*is_header, cells)
+        })
+        .collect();
+    let mut widths = column_widths(&rows);
+    if let Some((_, first_row)) = rows.first() {
+        widen_for_alignment(&mut widths, first_row, columns);
+    }
+    let dashes = column_dashes(&widths);
+    let mut markdown = String::new();
+    let has_header = rows.first().is_some_and(|(is_header, _)| *is_header);
+    for (i, (_, cells)) in rows.iter().enumerate() {
+        if i == 0 && !has_header {
+            markdown += &dashes;
+        }
+        markdown += &row_lines(&widths, cells, columns);
+        if i == 0 && has_header {
+            markdown += &dashes;
+        }
+    }
+    if !has_header {
+        markdown += &dashes;
+    }
+    markdown
+}
+
+/// The width at which the text of cells in multiline tables wraps.
+const MULTILINE_WIDTH: usize = 30;
+
+/// Wrap the Markdown of a cell at spaces, to lines of at most `width` characters where possible.
+/// Escaped spaces, like in `H~2\ O~`, are kept.
+fn wrap(markdown: &str, width: usize) -> Vec<String> {
+    let mut words = vec![];
+    let mut word = String::new();
+    let mut chars = markdown.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                word.push(c);
+                word.extend(chars.next());
+            }
+            ' ' if !word.is_empty() => words.push(std::mem::take(&mut word)),
+            ' ' => (),
+            _ => word.push(c),
+        }
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    let mut lines = vec![String::new()];
+    for word in words {
+        let line = lines.last_mut().unwrap();
+        if line.is_empty() {
+            *line = word;
+        } else if display_width(line) + 1 + display_width(&word) > width {
+            lines.push(word);
+        } else {
+            line.push(' ');
+            line.push_str(&word);
+        }
+    }
+    lines
+}
+
+/// A pandoc multiline table, in which the text of cells wraps over several lines, and rows are
+/// separated by an empty line. The first row is its header when it is a header row. The columns
+/// are aligned by the position of their text, and their widths follow those in Word.
+fn multiline_table(rows: &[(bool, Vec<String>)], columns: &Columns) -> String {
+    let rows: Vec<(bool, Vec<Vec<String>>)> = rows
+        .iter()
+        .map(|(is_header, cells)| {
+            let cells = cells
+                .iter()
+                .map(|cell| wrap(cell, MULTILINE_WIDTH))
+                .collect();
+            (*is_header, cells)
+        })
+        .collect();
+    let mut widths = columns.proportional(&column_widths(&rows));
+    if let Some((_, first_row)) = rows.first() {
+        widen_for_alignment(&mut widths, first_row, columns);
+    }
+    let dashes = column_dashes(&widths);
+    let has_header = rows.first().is_some_and(|(is_header, _)| *is_header);
+    let mut markdown = String::new();
+    let mut body = rows.iter();
+    if has_header {
+        let line = "-".repeat(widths.iter().sum::<usize>() + 2 * (widths.len() - 1)) + "\n";
+        markdown += &line;
+        markdown += &row_lines(&widths, &rows[0].1, columns);
+        markdown += &dashes;
+        markdown += &body
+            .by_ref()
+            .skip(1)
+            .map(|(_, cells)| row_lines(&widths, cells, columns))
+            .collect::<Vec<_>>()
+            .join("\n");
+        markdown += &line;
+    } else {
+        markdown += &dashes;
+        markdown += &body
+            .map(|(_, cells)| row_lines(&widths, cells, columns))
+            .collect::<Vec<_>>()
+            .join("\n");
+        markdown += &dashes;
+    }
+    markdown
+}
+
+/// An HTML table, with the leading header rows in `<thead>`, the relative widths of the columns
+/// in Word in a `<colgroup>`, and the alignment of each cell.
+pub(crate) fn table_to_html(
+    table: &MarkdownTable,
+    mut cell_to_markdown: impl FnMut(&MarkdownTableCell, CellFormat) -> Vec<String>,
+) -> String {
+    let rows = render_rows(table, &mut cell_to_markdown, CellFormat::Html);
+    let header_rows = rows.iter().take_while(|(is_header, _)| *is_header).count();
+    let mut html = "<table>\n".to_string();
+    let columns = Columns::of(table);
+    if let Some(widths) = columns.widths.as_ref().filter(|widths| !widths.is_empty()) {
+        let total: usize = widths.iter().sum();
+        html += "<colgroup>\n";
+        for width in widths {
+            let percentage = (width * 100 + total / 2).checked_div(total);
+            html += &format!(
+                "<col style=\"width: {}%\" />\n",
+                percentage.unwrap_or_default()
+            );
+        }
+        html += "</colgroup>\n";
+    }
+    for (i, (is_header, cells)) in rows.iter().enumerate() {
+        if i == 0 && header_rows > 0 {
+            html += "<thead>\n";
+        }
+        if i == header_rows {
+            html += "<tbody>\n";
+        }
+        html += "<tr>\n";
+        let tag = if *is_header { "th" } else { "td" };
+        for (cell, blocks) in cells {
+            html += &format!("<{tag}");
+            if cell.colspan > 1 {
+                html += &format!(" colspan=\"{}\"", cell.colspan);
+            }
+            if cell.rowspan > 1 {
+                html += &format!(" rowspan=\"{}\"", cell.rowspan);
+            }
+            match cell.alignment {
+                Some(Alignment::Center) => html += " style=\"text-align: center;\"",
+                Some(Alignment::Right) => html += " style=\"text-align: right;\"",
+                _ => (),
+            }
+            html += ">";
+            // Several paragraphs are each wrapped in `<p>`, and they and nested tables start on a
+            // new line
+            let is_table = |markdown: &String| markdown.starts_with("<table>");
+            let wrap_paragraphs = blocks.iter().filter(|block| !is_table(block)).count() > 1;
+            for (i, markdown) in blocks.iter().enumerate() {
+                if i > 0 {
+                    html += "\n";
+                }
+                let markdown = markdown.trim_end_matches('\n');
+                if wrap_paragraphs && !is_table(&blocks[i]) {
+                    html += &format!("<p>{markdown}</p>");
+                } else {
+                    html += markdown;
+                }
+            }
+            html += &format!("</{tag}>\n");
+        }
+        html += "</tr>\n";
+        if i + 1 == header_rows {
+            html += "</thead>\n";
+        }
+    }
+    if header_rows < rows.len() {
+        html += "</tbody>\n";
+    }
+    html += "</table>\n";
+    html
+}
+
+/// A cell that is placed on the grid of a grid table.
+struct GridCell {
+    row: usize,
+    column: usize,
+    rowspan: usize,
+    colspan: usize,
+    lines: Vec<String>,
+}
+
+/// A pandoc grid table, in which cells can span columns and rows, and the blocks of a cell
+/// are separated by an empty line. A line of `=` ends the leading header rows. The alignment of
+/// the columns is marked in that line, or in the top line when the table has no header, e.g.
+/// `+:===+===:+`, and the widths of the columns follow those in Word.
+fn grid_table(table: &MarkdownTable, rows: &[RenderedRow], columns: &Columns) -> String {
+    let (placed, column_count) = place_cells(table);
+    if column_count == 0 {
+        return String::new();
+    }
+    let mut cells: Vec<GridCell> = vec![];
+    for (row, ((_, row_cells), placed)) in rows.iter().zip(&placed).enumerate() {
+        for ((cell, blocks), column) in row_cells.iter().zip(placed) {
+            cells.push(GridCell {
+                row,
+                column: *column,
+                rowspan: cell.rowspan.min(rows.len() - row),
+                colspan: cell.colspan,
+                lines: blocks
+                    .iter()
+                    .map(|block| block.trim_end_matches('\n'))
+                    .collect::<Vec<_>>()
+                    .join("\n\n")
+                    .lines()
+                    .map(str::to_string)
+                    .collect(),
+            });
+        }
+    }
+
+    // Widen the columns and heighten the rows until each cell fits, where a cell that spans
+    // several columns or rows also has the space of the borders between them
+    let mut widths = vec![3; column_count];
+    let mut heights = vec![1; rows.len()];
+    for cell in cells.iter().filter(|cell| cell.colspan == 1) {
+        let width = cell.lines.iter().map(|line| display_width(line)).max();
+        widths[cell.column] = widths[cell.column].max(width.unwrap_or_default());
+    }
+    let mut widths = columns.proportional(&widths);
+    for cell in cells.iter().filter(|cell| cell.colspan > 1) {
+        let width = cell.lines.iter().map(|line| display_width(line)).max();
+        let spanned = &mut widths[cell.column..cell.column + cell.colspan];
+        let available: usize = spanned.iter().sum::<usize>() + 3 * (cell.colspan - 1);
+        if let Some(extra) = width.unwrap_or_default().checked_sub(available) {
+            spanned[cell.colspan - 1] += extra;
+        }
+    }
+    for cell in &cells {
+        let spanned = &mut heights[cell.row..cell.row + cell.rowspan];
+        let available: usize = spanned.iter().sum::<usize>() + cell.rowspan - 1;
+        if let Some(extra) = cell.lines.len().checked_sub(available) {
+            spanned[cell.rowspan - 1] += extra;
+        }
+    }
+
+    // Draw the cells on a canvas, where corners take precedence over edges. A wide character
+    // leaves the next position empty, and combining characters join the character before them
+    let mut x = vec![0];
+    for width in &widths {
+        x.push(x[x.len() - 1] + width + 3);
+    }
+    let mut y = vec![0];
+    for height in &heights {
+        y.push(y[y.len() - 1] + height + 1);
+    }
+    let mut canvas = vec![vec![" ".to_string(); x[column_count] + 1]; y[rows.len()] + 1];
+    let header_rows = rows.iter().take_while(|(is_header, _)| *is_header).count();
+    let draw = |canvas: &mut Vec<Vec<String>>, line: usize, column: usize, c: char| {
+        if canvas[line][column] != "+" {
+            canvas[line][column] = c.to_string();
+        }
+    };
+    for cell in &cells {
+        let (left, right) = (x[cell.column], x[cell.column + cell.colspan]);
+        let (top, bottom) = (y[cell.row], y[cell.row + cell.rowspan]);
+        for (line, is_header_line) in [
+            (top, cell.row == header_rows && header_rows > 0),
+            (bottom, cell.row + cell.rowspan == header_rows),
+        ] {
+            for column in left + 1..right {
+                draw(
+                    &mut canvas,
+                    line,
+                    column,
+                    if is_header_line { '=' } else { '-' },
+                );
+            }
+        }
+        for line in top + 1..bottom {
+            draw(&mut canvas, line, left, '|');
+            draw(&mut canvas, line, right, '|');
+        }
+        for (line, column) in [(top, left), (top, right), (bottom, left), (bottom, right)] {
+            canvas[line][column] = "+".to_string();
+        }
+        for (i, text) in cell.lines.iter().enumerate() {
+            // Each character takes the columns that it adds to the width of the text, so that
+            // sequences like emoji with modifiers take as many columns as they do together
+            let mut column = left + 2;
+            for (offset, c) in text.char_indices() {
+                let text_width = display_width(&text[..offset + c.len_utf8()]);
+                let width = (left + 2 + text_width)
+                    .saturating_sub(column)
+                    .min(right.saturating_sub(column + 1));
+                let line = &mut canvas[top + 1 + i];
+                if width > 0 {
+                    line[column] = c.to_string();
+                    line[column + 1..column + width].fill(String::new());
+                } else if let Some(previous) = line[..column].iter_mut().rfind(|s| !s.is_empty()) {
+                    previous.push(c);
+                }
+                column += width;
+            }
+        }
+    }
+    let alignment_line = if header_rows > 0 { y[header_rows] } else { 0 };
+    for column in 0..column_count {
+        let (left, right) = (x[column] + 1, x[column + 1] - 1);
+        let is_rule = |s: &str| matches!(s, "-" | "=");
+        if !is_rule(&canvas[alignment_line][left]) || !is_rule(&canvas[alignment_line][right]) {
+            continue;
+        }
+        let (colon_left, colon_right) = match columns.alignment(column) {
+            Some(Alignment::Center) => (true, true),
+            Some(Alignment::Right) => (false, true),
+            _ => (false, false),
+        };
+        if colon_left {
+            canvas[alignment_line][left] = ":".to_string();
+        }
+        if colon_right {
+            canvas[alignment_line][right] = ":".to_string();
+        }
+    }
+    canvas
+        .iter()
+        .map(|line| line.concat().trim_end().to_string() + "\n")
+        .collect()
+}
+
+#[test]
+fn test_wrap() {
+    assert_eq!(
+        vec!["A cell with", "text that", "wraps"],
+        wrap("A cell with  text that wraps", 11)
+    );
+    assert_eq!(vec!["H~2\\ O~", "water"], wrap("H~2\\ O~ water", 5));
+    assert_eq!(vec!["Unbreakable"], wrap("Unbreakable", 5));
+    assert_eq!(vec!["A\\"], wrap("A\\", 5));
+    assert_eq!(vec![""], wrap("", 5));
+}
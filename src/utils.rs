@@ -2,15 +2,41 @@ use base64::prelude::*;
 use serde::ser::SerializeMap;
 use serde::Serializer;
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     env,
     fs::{create_dir_all, File},
-    io::{self, Write},
+    io::{self, Read, Seek, SeekFrom, Write},
     path::PathBuf,
 };
+use unicode_width::UnicodeWidthStr;
+
+/// Signature of an OLE compound file, used by encrypted DOCX files and legacy .doc files.
+const COMPOUND_FILE_SIGNATURE: [u8; 8] = [0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Check whether the input is an OLE compound file instead of a zip archive.
+/// The reader is rewound to the start afterwards.
+pub fn is_compound_file<R: Read + Seek>(reader: &mut R) -> io::Result<bool> {
+    let mut signature = [0u8; 8];
+    let mut read = 0;
+    while read < signature.len() {
+        match reader.read(&mut signature[read..])? {
+            0 => break,
+            n => read += n,
+        }
+    }
+    reader.seek(SeekFrom::Start(0))?;
+    Ok(read == signature.len() && signature == COMPOUND_FILE_SIGNATURE)
+}
+
+/// The number of columns that the text takes up in a monospace font, where wide characters,
+/// like CJK ideographs and emoji, take two columns, and combining marks and zero width characters
+/// none, following the Unicode width rules.
+pub fn display_width(text: &str) -> usize {
+    UnicodeWidthStr::width(text)
+}
 
 pub fn max_lengths_per_column(
-    table_with_simple_cells: &Vec<(bool, Vec<String>)>,
+    table_with_simple_cells: &[(bool, Vec<String>)],
     min_width: usize,
 ) -> Vec<usize> {
     // Check if the table is empty
@@ -33,8 +59,8 @@ pub fn max_lengths_per_column(
                 max_lengths.push(0);
             }
             // Update the max length for the current column
-            if cell.len() > max_lengths[i] {
-                max_lengths[i] = cell.len();
+            if display_width(cell) > max_lengths[i] {
+                max_lengths[i] = display_width(cell);
             }
         }
     }
@@ -45,17 +71,17 @@ pub fn max_lengths_per_column(
 pub fn pad_left(s: &str, width: &usize) -> String {
     let mut padded = String::new();
     // If the string is already long enough, return it unchanged.
-    if *width <= s.len() {
+    if *width <= display_width(s) {
         return s.to_string();
     }
-    let padding = width - s.len();
+    let padding = width - display_width(s);
     // Add padding to the left of the string.
     padded.push_str(s);
     padded.push_str(&" ".repeat(padding));
     padded
 }
 
-pub fn table_row_to_markdown(column_lengths: &Vec<usize>, row: &Vec<String>) -> String {
+pub fn table_row_to_markdown(column_lengths: &[usize], row: &[String]) -> String {
     let mut table_row_in_markdown = "".to_string();
     column_lengths.iter().enumerate().for_each(|(j, width)| {
         let cell = if j < row.len() { &row[j] } else { "" };
@@ -65,6 +91,20 @@ pub fn table_row_to_markdown(column_lengths: &Vec<usize>, row: &Vec<String>) ->
     table_row_in_markdown
 }
 
+#[test]
+fn test_display_width() {
+    assert_eq!(5, display_width("Table"));
+    assert_eq!(4, display_width("Café"));
+    assert_eq!(4, display_width("Cafe\u{301}"));
+    assert_eq!(4, display_width("表格"));
+    assert_eq!(4, display_width("한국"));
+    assert_eq!(2, display_width("🎉"));
+    assert_eq!(2, display_width("🚀"));
+    assert_eq!(2, display_width("✅"));
+    assert_eq!(4, display_width("नमस्ते"));
+    assert_eq!(4, display_width("שָׁלוֹם"));
+}
+
 #[test]
 fn test_pad_left() {
     let text = "This is a test".to_string();
@@ -91,6 +131,102 @@ fn test_table_row_to_markdown() {
     );
 }
 
+/// The length of the longest run of `c` in the text, e.g. to choose a code fence that is longer.
+pub fn longest_run(text: &str, c: char) -> usize {
+    text.split(|other| other != c)
+        .map(|run| run.chars().count())
+        .max()
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_longest_run() {
+    assert_eq!(0, longest_run("no code", '`'));
+    assert_eq!(1, longest_run("`code`", '`'));
+    assert_eq!(3, longest_run("a ``` b `` c", '`'));
+}
+
+/// Prefix each line with `> `, once for each level of a nested block quote.
+pub fn quote(markdown: &str, depth: usize) -> String {
+    if depth == 0 {
+        return markdown.to_string();
+    }
+    let prefix = vec![">"; depth].join(" ");
+    markdown
+        .lines()
+        .map(|line| match line {
+            "" => prefix.clone(),
+            line => format!("{prefix} {line}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_quote() {
+    assert_eq!("text", quote("text", 0));
+    assert_eq!("> a\n>\n> b", quote("a\n\nb", 1));
+    assert_eq!("> > a", quote("a", 2));
+}
+
+/// Escape text for use in HTML content and (double-quoted) attribute values.
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[test]
+fn test_escape_html() {
+    assert_eq!(
+        escape_html(r#"Tom & "Jerry" <3"#),
+        "Tom &amp; &quot;Jerry&quot; &lt;3"
+    );
+}
+
+/// Quote text as a YAML double-quoted string.
+pub fn yaml_string(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{escaped}\"")
+}
+
+#[test]
+fn test_yaml_string() {
+    assert_eq!(
+        yaml_string("Say \"hi\"\nC:\\temp"),
+        r#""Say \"hi\"\nC:\\temp""#
+    );
+}
+
+/// A key for `name` that is not one of `keys` yet: the name itself, or else the name with the
+/// first free suffix, e.g. `name_2`. The key is added to `keys`.
+pub fn unique_key(name: &str, keys: &mut HashSet<String>) -> String {
+    let mut key = name.to_string();
+    let mut n = 1;
+    while keys.contains(&key) {
+        n += 1;
+        key = format!("{name}_{n}");
+    }
+    keys.insert(key.clone());
+    key
+}
+
+#[test]
+fn test_unique_key() {
+    let mut keys = HashSet::new();
+    let names = ["name", "name_2", "name", "name"];
+    let keys: Vec<String> = names
+        .iter()
+        .map(|name| unique_key(name, &mut keys))
+        .collect();
+    assert_eq!(vec!["name", "name_2", "name_3", "name_4"], keys);
+}
+
 pub fn save_image_to_file(path: &str, image_data: &[u8]) -> io::Result<()> {
     // Get the current working directory
     let current_dir = env::current_dir()?;
@@ -117,7 +253,7 @@ pub fn save_image_to_file(path: &str, image_data: &[u8]) -> io::Result<()> {
 }
 
 fn get_mime_type(filename: &str) -> Option<&'static str> {
-    let extension = filename.split('.').last()?;
+    let extension = filename.split('.').next_back()?;
     match extension.to_lowercase().as_str() {
         "png" => Some("image/png"),
         "jpg" | "jpeg" => Some("image/jpeg"),
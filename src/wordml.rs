@@ -0,0 +1,917 @@
+//! Document model for the WordprocessingML elements that docx-rust does not parse (yet), such as
+//! tracked changes, comments, notes, headers, content controls, level restarts of lists and VML
+//! horizontal lines. Leaf elements like run content and properties are reused from docx-rust, so only the
+//! containers that need to hold the extra elements are defined here.
+
+use docx_rust::document::{
+    AbstractNumId, BookmarkStart, CommentRangeEnd, CommentRangeStart, DocPartObj, Hyperlink,
+    LevelStart, LevelText, NumFmt, RunContent, StartOverride, TableGrid,
+};
+use docx_rust::formatting::{
+    Bold, Borders, Caps, CharacterProperty, CharacterStyleId, Color, Dstrike, Fonts, Highlight,
+    Indent, Italics, Justification, NumberingProperty, OutlineLvl, PageBreakBefore,
+    ParagraphProperty as DocxParagraphProperty, ParagraphStyleId, SectionProperty, Size, SmallCaps,
+    Strike, TableRowProperty, Underline, Vanish, VertAlign,
+};
+use docx_rust::rels::Relationships;
+use hard_xml::XmlRead;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+use crate::DocxParserError;
+
+/// The XML parts and media of a DOCX package, keyed by their path in the archive.
+#[derive(Debug, Default)]
+pub struct Parts {
+    parts: HashMap<String, String>,
+    media: HashMap<String, Vec<u8>>,
+}
+
+impl Parts {
+    /// Read the XML and relationship parts of the document from the archive, i.e. those in
+    /// `word/` and the document properties, and the files in `word/media/`. Other parts, like the
+    /// custom XML of SharePoint, are not used. Parts in UTF-16 are decoded by their byte order
+    /// mark, and parts in another encoding are an error.
+    pub fn read<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Result<Self, DocxParserError> {
+        let mut parts = Parts::default();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            let name = file.name().to_string();
+            let is_part = (name.starts_with("word/")
+                && (name.ends_with(".xml") || name.ends_with(".rels")))
+                || matches!(name.as_str(), "docProps/core.xml" | "docProps/app.xml");
+            if name.starts_with("word/media/") {
+                let mut content = vec![];
+                file.read_to_end(&mut content)?;
+                parts.media.insert(name, content);
+            } else if is_part {
+                let mut content = vec![];
+                file.read_to_end(&mut content)?;
+                let content = decode(&content).ok_or_else(|| {
+                    DocxParserError::Unsupported(format!("{name} is not UTF-8 or UTF-16"))
+                })?;
+                parts.parts.insert(name, content);
+            }
+        }
+        Ok(parts)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.parts.get(name).map(|part| part.as_str())
+    }
+
+    /// The files in `word/media/`, by their path relative to `word/`, e.g. `media/image1.png`.
+    pub fn media(&self) -> impl Iterator<Item = (&str, &[u8])> {
+        self.media.iter().map(|(name, content)| {
+            let name = name.strip_prefix("word/").unwrap_or(name);
+            (name, content.as_slice())
+        })
+    }
+
+    /// Read the relationships of a part, e.g. `word/_rels/footnotes.xml.rels` for
+    /// `word/footnotes.xml`, which are needed to resolve its links and images.
+    pub fn relationships(&self, name: &str) -> Result<Option<Relationships<'_>>, DocxParserError> {
+        let rels = match name.rsplit_once('/') {
+            Some((dir, file)) => format!("{dir}/_rels/{file}.rels"),
+            None => format!("_rels/{name}.rels"),
+        };
+        match self.get(&rels) {
+            Some(rels) => Ok(Some(Relationships::from_str(rels)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Decode an XML part, which XML requires to be UTF-8 or UTF-16 with a byte order mark.
+fn decode(content: &[u8]) -> Option<String> {
+    let utf16 = |to_u16: fn([u8; 2]) -> u16| {
+        let units: Vec<u16> = content[2..]
+            .chunks_exact(2)
+            .map(|unit| to_u16([unit[0], unit[1]]))
+            .collect();
+        String::from_utf16(&units).ok()
+    };
+    match content {
+        [0xFF, 0xFE, ..] => utf16(u16::from_le_bytes),
+        [0xFE, 0xFF, ..] => utf16(u16::from_be_bytes),
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8(rest.to_vec()).ok(),
+        _ => String::from_utf8(content.to_vec()).ok(),
+    }
+}
+
+/// The root element of the main document part.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:document")]
+pub struct Document<'a> {
+    #[xml(child = "w:body")]
+    pub body: Body<'a>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:body")]
+pub struct Body<'a> {
+    #[xml(child = "w:p", child = "w:tbl", child = "w:sectPr", child = "w:sdt")]
+    pub content: Vec<BodyContent<'a>>,
+}
+
+#[derive(Debug, XmlRead, Clone)]
+// hard_xml reads the variants by value, so they cannot be boxed
+#[allow(clippy::large_enum_variant)]
+pub enum BodyContent<'a> {
+    #[xml(tag = "w:p")]
+    Paragraph(Paragraph<'a>),
+    #[xml(tag = "w:tbl")]
+    Table(Table<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(BlockSdt<'a>),
+    #[xml(tag = "w:sectPr")]
+    SectionProperty(SectionProperty<'a>),
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:p")]
+pub struct Paragraph<'a> {
+    /// Identifies the paragraph, e.g. to link comment replies to their parent
+    #[xml(attr = "w14:paraId")]
+    pub para_id: Option<Cow<'a, str>>,
+    #[xml(child = "w:pPr")]
+    pub property: Option<ParagraphProperty<'a>>,
+    #[xml(
+        child = "w:commentRangeStart",
+        child = "w:commentRangeEnd",
+        child = "w:r",
+        child = "w:hyperlink",
+        child = "w:bookmarkStart",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:sdt",
+        child = "w:fldSimple",
+        child = "w:smartTag"
+    )]
+    pub content: Vec<ParagraphContent<'a>>,
+}
+
+#[derive(Debug, XmlRead, Clone)]
+pub enum ParagraphContent<'a> {
+    #[xml(tag = "w:commentRangeStart")]
+    CommentRangeStart(CommentRangeStart<'a>),
+    #[xml(tag = "w:commentRangeEnd")]
+    CommentRangeEnd(CommentRangeEnd<'a>),
+    #[xml(tag = "w:r")]
+    Run(Run<'a>),
+    #[xml(tag = "w:hyperlink")]
+    Link(Hyperlink<'a>),
+    #[xml(tag = "w:bookmarkStart")]
+    BookmarkStart(BookmarkStart<'a>),
+    #[xml(tag = "w:ins")]
+    Insertion(Insertion<'a>),
+    #[xml(tag = "w:del")]
+    Deletion(Deletion<'a>),
+    #[xml(tag = "w:moveFrom")]
+    MoveFrom(MoveFrom<'a>),
+    #[xml(tag = "w:moveTo")]
+    MoveTo(MoveTo<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(InlineSdt<'a>),
+    #[xml(tag = "w:fldSimple")]
+    SimpleField(SimpleField<'a>),
+    #[xml(tag = "w:smartTag")]
+    SmartTag(SmartTag<'a>),
+}
+
+/// A field without field codes in runs, e.g. `PAGE`, with its last result as content.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:fldSimple")]
+pub struct SimpleField<'a> {
+    #[xml(
+        child = "w:commentRangeStart",
+        child = "w:commentRangeEnd",
+        child = "w:r",
+        child = "w:hyperlink",
+        child = "w:bookmarkStart",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:sdt",
+        child = "w:fldSimple",
+        child = "w:smartTag"
+    )]
+    pub content: Vec<ParagraphContent<'a>>,
+}
+
+/// A smart tag, which marks text like a date or an address.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:smartTag")]
+pub struct SmartTag<'a> {
+    #[xml(
+        child = "w:commentRangeStart",
+        child = "w:commentRangeEnd",
+        child = "w:r",
+        child = "w:hyperlink",
+        child = "w:bookmarkStart",
+        child = "w:ins",
+        child = "w:del",
+        child = "w:moveFrom",
+        child = "w:moveTo",
+        child = "w:sdt",
+        child = "w:fldSimple",
+        child = "w:smartTag"
+    )]
+    pub content: Vec<ParagraphContent<'a>>,
+}
+
+/// A run like the one of docx-rust, which also reads VML pictures (`w:pict`), the way older
+/// documents store horizontal lines.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:r")]
+pub struct Run<'a> {
+    #[xml(child = "w:rPr")]
+    pub property: Option<CharacterProperty<'a>>,
+    #[xml(
+        child = "w:br",
+        child = "w:t",
+        child = "w:delText",
+        child = "w:instrText",
+        child = "w:delInstrText",
+        child = "w:noBreakHyphen",
+        child = "w:softHyphen",
+        child = "w:dayShort",
+        child = "w:monthShort",
+        child = "w:yearShort",
+        child = "w:dayLong",
+        child = "w:monthLong",
+        child = "w:yearLong",
+        child = "w:annotationRef",
+        child = "w:footnoteRef",
+        child = "w:endnoteRef",
+        child = "w:separator",
+        child = "w:continuationSeparator",
+        child = "w:sym",
+        child = "w:pgNum",
+        child = "w:cr",
+        child = "w:tab",
+        child = "w:fldChar",
+        child = "w:footnoteReference",
+        child = "w:endnoteReference",
+        child = "w:commentReference",
+        child = "w:drawing",
+        child = "w:ptab",
+        child = "w:lastRenderedPageBreak"
+    )]
+    pub content: Vec<RunContent<'a>>,
+    #[xml(child = "w:pict")]
+    pub picts: Vec<Pict<'a>>,
+}
+
+/// A VML picture.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:pict")]
+pub struct Pict<'a> {
+    #[xml(child = "v:rect")]
+    pub rects: Vec<VmlRect<'a>>,
+}
+
+impl Pict<'_> {
+    /// Whether the picture is a horizontal line, which Word inserts as a rectangle with `o:hr`.
+    pub fn is_horizontal_rule(&self) -> bool {
+        self.rects
+            .iter()
+            .any(|rect| matches!(rect.hr.as_deref(), Some("t" | "true")))
+    }
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "v:rect")]
+pub struct VmlRect<'a> {
+    #[xml(attr = "o:hr")]
+    pub hr: Option<Cow<'a, str>>,
+}
+
+/// The properties of a paragraph that are used, like in docx-rust, and the properties of the
+/// paragraph mark, which docx-rust reads without its tracked changes.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:pPr")]
+pub struct ParagraphProperty<'a> {
+    #[xml(child = "w:pStyle")]
+    pub style_id: Option<ParagraphStyleId<'a>>,
+    #[xml(child = "w:pageBreakBefore")]
+    pub page_break_before: Option<PageBreakBefore>,
+    #[xml(child = "w:numPr")]
+    pub numbering: Option<NumberingProperty<'a>>,
+    #[xml(child = "w:pBdr")]
+    pub border: Option<Borders<'a>>,
+    #[xml(child = "w:ind")]
+    pub indent: Option<Indent>,
+    #[xml(child = "w:jc")]
+    pub justification: Option<Justification>,
+    #[xml(child = "w:outlineLvl")]
+    pub outline_lvl: Option<OutlineLvl>,
+    #[xml(child = "w:rPr")]
+    pub mark: Option<ParagraphMarkProperty<'a>>,
+    #[xml(child = "w:sectPr")]
+    pub section_property: Option<SectionProperty<'a>>,
+}
+
+impl<'a> ParagraphProperty<'a> {
+    /// The same properties as those of docx-rust.
+    pub fn to_docx(&self) -> DocxParagraphProperty<'a> {
+        DocxParagraphProperty {
+            style_id: self.style_id.clone(),
+            page_break_before: self.page_break_before.clone(),
+            numbering: self.numbering.clone(),
+            border: self.border.clone(),
+            indent: self.indent.clone(),
+            justification: self.justification.clone(),
+            outline_lvl: self.outline_lvl.clone(),
+            r_pr: self
+                .mark
+                .iter()
+                .map(ParagraphMarkProperty::to_docx)
+                .collect(),
+            section_property: self.section_property.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// The run properties of the paragraph mark that are used, and whether the mark itself, i.e.
+/// the end of the paragraph, is a tracked change.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:rPr")]
+pub struct ParagraphMarkProperty<'a> {
+    #[xml(child = "w:ins")]
+    pub insertion: Option<Insertion<'a>>,
+    #[xml(child = "w:del")]
+    pub deletion: Option<Deletion<'a>>,
+    #[xml(child = "w:moveFrom")]
+    pub move_from: Option<MoveFrom<'a>>,
+    #[xml(child = "w:moveTo")]
+    pub move_to: Option<MoveTo<'a>>,
+    #[xml(child = "w:rStyle")]
+    pub style_id: Option<CharacterStyleId<'a>>,
+    #[xml(child = "w:rFonts")]
+    pub fonts: Option<Fonts>,
+    #[xml(child = "w:b")]
+    pub bold: Option<Bold>,
+    #[xml(child = "w:i")]
+    pub italics: Option<Italics>,
+    #[xml(child = "w:caps")]
+    pub caps: Option<Caps>,
+    #[xml(child = "w:smallCaps")]
+    pub small_caps: Option<SmallCaps>,
+    #[xml(child = "w:strike")]
+    pub strike: Option<Strike>,
+    #[xml(child = "w:dstrike")]
+    pub dstrike: Option<Dstrike>,
+    #[xml(child = "w:vanish")]
+    pub vanish: Option<Vanish>,
+    #[xml(child = "w:color")]
+    pub color: Option<Color<'a>>,
+    #[xml(child = "w:sz")]
+    pub size: Option<Size>,
+    #[xml(child = "w:highlight")]
+    pub highlight: Option<Highlight>,
+    #[xml(child = "w:u")]
+    pub underline: Option<Underline<'a>>,
+    #[xml(child = "w:vertAlign")]
+    pub vertical_align: Option<VertAlign>,
+}
+
+impl<'a> ParagraphMarkProperty<'a> {
+    /// The same run properties as those of docx-rust.
+    pub fn to_docx(&self) -> CharacterProperty<'a> {
+        CharacterProperty {
+            style_id: self.style_id.clone(),
+            fonts: self.fonts.clone(),
+            bold: self.bold.clone(),
+            italics: self.italics.clone(),
+            caps: self.caps.clone(),
+            small_caps: self.small_caps.clone(),
+            strike: self.strike.clone(),
+            dstrike: self.dstrike.clone(),
+            vanish: self.vanish.clone(),
+            color: self.color.clone(),
+            size: self.size.clone(),
+            highlight: self.highlight.clone(),
+            underline: self.underline.clone(),
+            vertical_align: self.vertical_align.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Defines a tracked change that wraps paragraph content, usually runs. In the properties of a
+/// paragraph mark, it is empty.
+macro_rules! revision {
+    ($(#[$doc:meta])* $tag:literal, $name:ident) => {
+        $(#[$doc])*
+        #[derive(Debug, Default, XmlRead, Clone)]
+        #[xml(tag = $tag)]
+        pub struct $name<'a> {
+            #[xml(attr = "w:author")]
+            pub author: Option<Cow<'a, str>>,
+            #[xml(attr = "w:date")]
+            pub date: Option<Cow<'a, str>>,
+            #[xml(
+                child = "w:commentRangeStart",
+                child = "w:commentRangeEnd",
+                child = "w:r",
+                child = "w:hyperlink",
+                child = "w:bookmarkStart",
+                child = "w:ins",
+                child = "w:del",
+                child = "w:moveFrom",
+                child = "w:moveTo",
+                child = "w:sdt",
+                child = "w:fldSimple",
+                child = "w:smartTag"
+            )]
+            pub content: Vec<ParagraphContent<'a>>,
+        }
+    };
+}
+
+revision!(
+    /// Inserted content
+    "w:ins",
+    Insertion
+);
+revision!(
+    /// Deleted content, whose text is stored as `w:delText`
+    "w:del",
+    Deletion
+);
+revision!(
+    /// Content that was moved away from this location
+    "w:moveFrom",
+    MoveFrom
+);
+revision!(
+    /// Content that was moved to this location
+    "w:moveTo",
+    MoveTo
+);
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:tbl")]
+pub struct Table<'a> {
+    #[xml(default, child = "w:tblGrid")]
+    pub grids: TableGrid,
+    #[xml(child = "w:tr", child = "w:sdt")]
+    pub rows: Vec<TableContent<'a>>,
+}
+
+impl<'a> Table<'a> {
+    /// The rows of the table, including the ones in content controls.
+    pub fn rows(&self) -> Vec<&TableRow<'a>> {
+        fn collect<'a, 'b>(content: &'b [TableContent<'a>], rows: &mut Vec<&'b TableRow<'a>>) {
+            for content in content {
+                match content {
+                    TableContent::TableRow(row) => rows.push(row),
+                    TableContent::Sdt(sdt) => collect(&sdt.content.content, rows),
+                }
+            }
+        }
+        let mut rows = vec![];
+        collect(&self.rows, &mut rows);
+        rows
+    }
+}
+
+#[derive(Debug, XmlRead, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum TableContent<'a> {
+    #[xml(tag = "w:tr")]
+    TableRow(TableRow<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(TableSdt<'a>),
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:tr")]
+pub struct TableRow<'a> {
+    #[xml(default, child = "w:trPr")]
+    pub property: TableRowProperty,
+    #[xml(child = "w:tc", child = "w:sdt")]
+    pub cells: Vec<TableRowContent<'a>>,
+}
+
+impl<'a> TableRow<'a> {
+    /// The cells of the row, including the ones in content controls.
+    pub fn cells(&self) -> Vec<&TableCell<'a>> {
+        fn collect<'a, 'b>(content: &'b [TableRowContent<'a>], cells: &mut Vec<&'b TableCell<'a>>) {
+            for content in content {
+                match content {
+                    TableRowContent::TableCell(cell) => cells.push(cell),
+                    TableRowContent::Sdt(sdt) => collect(&sdt.content.content, cells),
+                }
+            }
+        }
+        let mut cells = vec![];
+        collect(&self.cells, &mut cells);
+        cells
+    }
+}
+
+#[derive(Debug, XmlRead, Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum TableRowContent<'a> {
+    #[xml(tag = "w:tc")]
+    TableCell(TableCell<'a>),
+    #[xml(tag = "w:sdt")]
+    Sdt(RowSdt<'a>),
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:tc")]
+pub struct TableCell<'a> {
+    #[xml(default, child = "w:tcPr")]
+    pub property: TableCellProperty<'a>,
+    /// The paragraphs, nested tables and content controls of the cell
+    #[xml(child = "w:p", child = "w:tbl", child = "w:sdt")]
+    pub content: Vec<BodyContent<'a>>,
+}
+
+/// The properties of a table cell. docx-rust does not read how cells are merged.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:tcPr")]
+pub struct TableCellProperty<'a> {
+    /// The number of grid columns that the cell spans
+    #[xml(child = "w:gridSpan")]
+    pub grid_span: Option<GridSpan>,
+    /// Merges the cell with the one above it, unless the value is `restart`, which starts a
+    /// vertically merged cell
+    #[xml(child = "w:vMerge")]
+    pub v_merge: Option<VMerge<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:gridSpan")]
+pub struct GridSpan {
+    #[xml(attr = "w:val")]
+    pub value: Option<usize>,
+}
+
+/// The root element of the comments part, `word/comments.xml`.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:comments")]
+pub struct Comments<'a> {
+    #[xml(child = "w:comment")]
+    pub comments: Vec<Comment<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:comment")]
+pub struct Comment<'a> {
+    #[xml(attr = "w:id")]
+    pub id: Cow<'a, str>,
+    #[xml(attr = "w:author")]
+    pub author: Option<Cow<'a, str>>,
+    #[xml(attr = "w:date")]
+    pub date: Option<Cow<'a, str>>,
+    #[xml(child = "w:p")]
+    pub content: Vec<Paragraph<'a>>,
+}
+
+/// The root element of `word/commentsExtended.xml`, which links replies to their parent comment.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w15:commentsEx")]
+pub struct CommentsExtended<'a> {
+    #[xml(child = "w15:commentEx")]
+    pub comments: Vec<CommentExtended<'a>>,
+}
+
+/// Extra information about the comment whose last paragraph has the same `para_id`.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w15:commentEx")]
+pub struct CommentExtended<'a> {
+    #[xml(attr = "w15:paraId")]
+    pub para_id: Cow<'a, str>,
+    #[xml(attr = "w15:paraIdParent")]
+    pub para_id_parent: Option<Cow<'a, str>>,
+}
+
+/// The root element of the footnotes part, `word/footnotes.xml`.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:footnotes")]
+pub struct FootNotes<'a> {
+    #[xml(child = "w:footnote")]
+    pub notes: Vec<FootNote<'a>>,
+}
+
+/// The root element of the endnotes part, `word/endnotes.xml`.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:endnotes")]
+pub struct EndNotes<'a> {
+    #[xml(child = "w:endnote")]
+    pub notes: Vec<EndNote<'a>>,
+}
+
+/// Defines a footnote or endnote.
+macro_rules! note {
+    ($tag:literal, $name:ident) => {
+        #[derive(Debug, Default, XmlRead, Clone)]
+        #[xml(tag = $tag)]
+        pub struct $name<'a> {
+            #[xml(attr = "w:id")]
+            pub id: Cow<'a, str>,
+            /// Separators have a type, regular notes do not
+            #[xml(attr = "w:type")]
+            pub ty: Option<Cow<'a, str>>,
+            #[xml(child = "w:p")]
+            pub content: Vec<Paragraph<'a>>,
+        }
+    };
+}
+
+note!("w:footnote", FootNote);
+note!("w:endnote", EndNote);
+
+/// The root element of a header part, e.g. `word/header1.xml`.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:hdr")]
+pub struct Header<'a> {
+    #[xml(child = "w:p", child = "w:tbl", child = "w:sdt")]
+    pub content: Vec<BodyContent<'a>>,
+}
+
+/// The root element of a footer part, e.g. `word/footer1.xml`.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:ftr")]
+pub struct Footer<'a> {
+    #[xml(child = "w:p", child = "w:tbl", child = "w:sdt")]
+    pub content: Vec<BodyContent<'a>>,
+}
+
+/// Defines a structured document tag (content control) and its content, which depends on
+/// where the tag is used.
+macro_rules! sdt {
+    ($(#[$doc:meta])* $name:ident, $content:ident, $item:ident, $($child:literal),+) => {
+        $(#[$doc])*
+        #[derive(Debug, Default, XmlRead, Clone)]
+        #[xml(tag = "w:sdt")]
+        pub struct $name<'a> {
+            // Not read for the content controls around rows and cells
+            #[allow(dead_code)]
+            #[xml(default, child = "w:sdtPr")]
+            pub property: SdtProperty<'a>,
+            #[xml(default, child = "w:sdtContent")]
+            pub content: $content<'a>,
+        }
+
+        #[derive(Debug, Default, XmlRead, Clone)]
+        #[xml(tag = "w:sdtContent")]
+        pub struct $content<'a> {
+            #[xml($(child = $child),+)]
+            pub content: Vec<$item<'a>>,
+        }
+    };
+}
+
+sdt!(
+    /// Content control around paragraphs and tables
+    BlockSdt,
+    BlockSdtContent,
+    BodyContent,
+    "w:p",
+    "w:tbl",
+    "w:sdt"
+);
+sdt!(
+    /// Content control inside a paragraph
+    InlineSdt,
+    InlineSdtContent,
+    ParagraphContent,
+    "w:commentRangeStart",
+    "w:commentRangeEnd",
+    "w:r",
+    "w:hyperlink",
+    "w:bookmarkStart",
+    "w:ins",
+    "w:del",
+    "w:moveFrom",
+    "w:moveTo",
+    "w:sdt",
+    "w:fldSimple",
+    "w:smartTag"
+);
+sdt!(
+    /// Content control around table rows
+    TableSdt,
+    TableSdtContent,
+    TableContent,
+    "w:tr",
+    "w:sdt"
+);
+sdt!(
+    /// Content control around table cells
+    RowSdt,
+    RowSdtContent,
+    TableRowContent,
+    "w:tc",
+    "w:sdt"
+);
+
+/// The properties of a content control. Its type is determined by which of the type elements,
+/// like `w:text` or `w:date`, is present; without any, it is a rich text control.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:sdtPr")]
+pub struct SdtProperty<'a> {
+    #[xml(child = "w:id")]
+    pub id: Option<SdtId<'a>>,
+    #[xml(child = "w:alias")]
+    pub alias: Option<SdtAlias<'a>>,
+    #[xml(child = "w:tag")]
+    pub tag: Option<SdtTag<'a>>,
+    #[xml(child = "w:showingPlcHdr")]
+    pub showing_placeholder: Option<ShowingPlaceholder>,
+    #[xml(child = "w:text")]
+    pub text: Option<SdtText<'a>>,
+    #[xml(child = "w:richText")]
+    pub rich_text: Option<RichText>,
+    #[xml(child = "w:dropDownList")]
+    pub drop_down_list: Option<DropDownList<'a>>,
+    #[xml(child = "w:comboBox")]
+    pub combo_box: Option<ComboBox<'a>>,
+    #[xml(child = "w:date")]
+    pub date: Option<SdtDate<'a>>,
+    #[xml(child = "w14:checkbox")]
+    pub checkbox: Option<CheckBox<'a>>,
+    #[xml(child = "w:picture")]
+    pub picture: Option<Picture>,
+    #[xml(child = "w:docPartObj")]
+    pub doc_part_obj: Option<DocPartObj<'a>>,
+    #[xml(child = "w:docPartList")]
+    pub doc_part_list: Option<DocPartObj<'a>>,
+    #[xml(child = "w:group")]
+    pub group: Option<Group>,
+    #[xml(child = "w:citation")]
+    pub citation: Option<Citation>,
+    #[xml(child = "w:bibliography")]
+    pub bibliography: Option<Bibliography>,
+    #[xml(child = "w:equation")]
+    pub equation: Option<Equation>,
+}
+
+/// Defines an element with a single `w:val` attribute.
+macro_rules! value {
+    ($tag:literal, $name:ident) => {
+        #[derive(Debug, Default, XmlRead, Clone)]
+        #[xml(tag = $tag)]
+        pub struct $name<'a> {
+            #[xml(attr = "w:val")]
+            pub value: Option<Cow<'a, str>>,
+        }
+    };
+}
+
+value!("w:id", SdtId);
+value!("w:alias", SdtAlias);
+value!("w:tag", SdtTag);
+value!("w:dateFormat", DateFormat);
+value!("w:vMerge", VMerge);
+
+/// Defines an element without attributes or children that are used.
+macro_rules! marker {
+    ($tag:literal, $name:ident) => {
+        #[derive(Debug, Default, XmlRead, Clone)]
+        #[xml(tag = $tag)]
+        pub struct $name;
+    };
+}
+
+marker!("w:showingPlcHdr", ShowingPlaceholder);
+marker!("w:richText", RichText);
+marker!("w:picture", Picture);
+marker!("w:group", Group);
+marker!("w:citation", Citation);
+marker!("w:bibliography", Bibliography);
+marker!("w:equation", Equation);
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:text")]
+pub struct SdtText<'a> {
+    #[xml(attr = "w:multiLine")]
+    pub multi_line: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:dropDownList")]
+pub struct DropDownList<'a> {
+    #[xml(child = "w:listItem")]
+    pub items: Vec<ListItem<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:comboBox")]
+pub struct ComboBox<'a> {
+    #[xml(child = "w:listItem")]
+    pub items: Vec<ListItem<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:listItem")]
+pub struct ListItem<'a> {
+    #[xml(attr = "w:displayText")]
+    pub display_text: Option<Cow<'a, str>>,
+    #[xml(attr = "w:value")]
+    pub value: Option<Cow<'a, str>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:date")]
+pub struct SdtDate<'a> {
+    /// The selected date, e.g. `2024-01-31T00:00:00Z`
+    #[xml(attr = "w:fullDate")]
+    pub full_date: Option<Cow<'a, str>>,
+    #[xml(child = "w:dateFormat")]
+    pub format: Option<DateFormat<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w14:checkbox")]
+pub struct CheckBox<'a> {
+    #[xml(child = "w14:checked")]
+    pub checked: Option<Checked<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w14:checked")]
+pub struct Checked<'a> {
+    #[xml(attr = "w14:val")]
+    pub value: Option<Cow<'a, str>>,
+}
+
+/// Numbering definitions, including the level restarts and level overrides that docx-rust
+/// leaves out.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:numbering")]
+pub struct Numbering<'a> {
+    #[xml(child = "w:abstractNum")]
+    pub abstract_numberings: Vec<AbstractNum<'a>>,
+    #[xml(child = "w:num")]
+    pub numberings: Vec<Num<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:abstractNum")]
+pub struct AbstractNum<'a> {
+    #[xml(attr = "w:abstractNumId")]
+    pub abstract_num_id: Option<isize>,
+    #[xml(child = "w:lvl")]
+    pub levels: Vec<Level<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:lvl")]
+pub struct Level<'a> {
+    #[xml(attr = "w:ilvl")]
+    pub i_level: Option<isize>,
+    #[xml(child = "w:start")]
+    pub start: Option<LevelStart>,
+    #[xml(child = "w:numFmt")]
+    pub number_format: Option<NumFmt<'a>>,
+    #[xml(child = "w:lvlRestart")]
+    pub restart: Option<LevelRestart>,
+    #[xml(child = "w:isLgl")]
+    pub is_legal: Option<IsLegal>,
+    #[xml(child = "w:lvlText")]
+    pub level_text: Option<LevelText<'a>>,
+}
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:lvlRestart")]
+pub struct LevelRestart {
+    #[xml(attr = "w:val")]
+    pub value: Option<isize>,
+}
+
+marker!("w:isLgl", IsLegal);
+
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:num")]
+pub struct Num<'a> {
+    #[xml(attr = "w:numId")]
+    pub num_id: Option<isize>,
+    #[xml(child = "w:abstractNumId")]
+    pub abstract_num_id: Option<AbstractNumId>,
+    #[xml(child = "w:lvlOverride")]
+    pub level_overrides: Vec<LevelOverride<'a>>,
+}
+
+/// Overrides the start value or the whole definition of a level for one list.
+#[derive(Debug, Default, XmlRead, Clone)]
+#[xml(tag = "w:lvlOverride")]
+pub struct LevelOverride<'a> {
+    #[xml(attr = "w:ilvl")]
+    pub i_level: Option<isize>,
+    #[xml(child = "w:startOverride")]
+    pub start_override: Option<StartOverride>,
+    #[xml(child = "w:lvl")]
+    pub level: Option<Level<'a>>,
+}